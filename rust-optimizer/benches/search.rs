@@ -0,0 +1,62 @@
+//! Throughput of minimax, serial alpha-beta and parallel alpha-beta at
+//! depths 3 to 7, all searching the same seeded position so runs are
+//! comparable. Run with `cargo bench`; `cargo bench -- <filter>` runs only
+//! the benchmarks whose name matches the filter, e.g. `alpha_beta/5`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::hint::black_box;
+use std::time::Duration;
+use strategic_mind_optimizer::{AlphaBetaSearch, GameState, MinimaxSearch, Phase, Player, SearchResult};
+
+const SEED: u64 = 24301;
+const DEPTHS: std::ops::RangeInclusive<u8> = 3..=7;
+
+fn bench_state() -> GameState {
+    GameState {
+        round: 5,
+        phase: Phase::Claim,
+        player1_trust: 55,
+        player2_trust: 45,
+        current_claim: None,
+        move_history: Vec::new(),
+    }
+}
+
+/// Benchmark `search` at every depth in one group named after the engine,
+/// with throughput counted in the nodes a search of that depth explores
+fn bench_engine(c: &mut Criterion, engine: &str, search: impl Fn(u8, &GameState) -> SearchResult) {
+    let state = bench_state();
+    let mut group = c.benchmark_group(engine);
+    // The deepest searches take long enough that fewer samples will do
+    group.sample_size(10).measurement_time(Duration::from_secs(5));
+
+    for depth in DEPTHS {
+        group.throughput(Throughput::Elements(search(depth, &state).nodes_explored));
+        group.bench_with_input(BenchmarkId::from_parameter(depth), &depth, |b, &depth| {
+            b.iter(|| search(black_box(depth), black_box(&state)))
+        });
+    }
+
+    group.finish();
+}
+
+fn minimax(c: &mut Criterion) {
+    bench_engine(c, "minimax", |depth, state| {
+        MinimaxSearch::new(depth).with_seed(SEED).search(state, Player::Player1)
+    });
+}
+
+fn alpha_beta(c: &mut Criterion) {
+    bench_engine(c, "alpha_beta", |depth, state| {
+        AlphaBetaSearch::new(depth, false).with_seed(SEED).search(state, Player::Player1)
+    });
+}
+
+fn parallel_alpha_beta(c: &mut Criterion) {
+    bench_engine(c, "parallel_alpha_beta", |depth, state| {
+        AlphaBetaSearch::new(depth, true).with_seed(SEED).search(state, Player::Player1)
+    });
+}
+
+criterion_group!(benches, minimax, alpha_beta, parallel_alpha_beta);
+criterion_main!(benches);
@@ -1,13 +1,10 @@
 fn main() {
     println!("cargo:rerun-if-changed=src/");
-    
-    // Generate JNI headers if needed
-    #[cfg(target_os = "windows")]
-    println!("cargo:rustc-link-lib=dylib=jvm");
-    
-    #[cfg(target_os = "linux")]
-    println!("cargo:rustc-link-lib=dylib=jvm");
-    
-    #[cfg(target_os = "macos")]
-    println!("cargo:rustc-link-lib=dylib=jvm");
-}
\ No newline at end of file
+
+    // Link the JVM on the platforms it exists for. The target, not the
+    // host, decides: there is none to link for wasm32.
+    let target_os = std::env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
+    if matches!(target_os.as_str(), "windows" | "linux" | "macos") {
+        println!("cargo:rustc-link-lib=dylib=jvm");
+    }
+}
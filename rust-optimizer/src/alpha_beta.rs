@@ -1,215 +1,2012 @@
-use crate::{GameTree, GameState, Move, Player, SearchResult};
-use crate::evaluation::Evaluator;
-use std::time::Instant;
+use crate::{Action, ClaimLevels, ClaimType, GameTree, GameState, Move, Phase, Player, SearchResult};
+use crate::rules::{mate_value, GameOutcome, GameRules, NoMovePolicy, MATE_SCORE};
+use crate::analysis::CachedAnalysis;
+use crate::cache::{CacheStats, LruCache};
+use crate::equilibrium::regret_matching;
+use crate::instrument;
+use crate::evaluation::{Evaluator, StateEvaluator};
+use crate::opening_book::OpeningBook;
+use crate::opponent::OpponentModel;
+use crate::profile::{BoldnessProfile, RiskProfile};
+use std::sync::Arc;
+use std::sync::mpsc::Sender;
+use crate::time::{Duration, Instant};
 use rayon::prelude::*;
+use std::collections::HashMap;
+
+/// How the random outcomes of a challenge are folded into a single value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChanceMode {
+    /// Sample one outcome per move, as `GameTree::apply_move` does
+    Sampled,
+    /// Weight every outcome by its probability (expectiminimax)
+    Expected,
+    /// Assume the worst outcome for the searching player (maximin)
+    WorstCase,
+}
+
+/// How much of the remaining search depth a ply uses up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DepthPolicy {
+    /// Every ply costs one
+    #[default]
+    Uniform,
+    /// A claim, the ply into the challenge phase, costs nothing up to
+    /// `max_extensions` times on a line, so the search sees how each claim
+    /// is answered without giving up depth for it
+    ExtendChallenges { max_extensions: u8 },
+}
+
+impl DepthPolicy {
+    /// Whether `move_made` is searched at the depth of the node it is
+    /// played at, with `extensions` already taken on the line above it
+    fn extends(&self, move_made: &Move, extensions: u8) -> bool {
+        match *self {
+            DepthPolicy::Uniform => false,
+            DepthPolicy::ExtendChallenges { max_extensions } => {
+                move_made.action == Action::MakeClaim && extensions < max_extensions
+            }
+        }
+    }
+}
 
 /// Alpha-Beta pruning search with parallel optimization
 pub struct AlphaBetaSearch {
-    evaluator: Evaluator,
+    evaluator: Arc<dyn StateEvaluator>,
     max_depth: u8,
     nodes_explored: u64,
     enable_parallel: bool,
+    /// Pool the parallel root split runs in, or `None` for the global pool
+    thread_pool: Option<Arc<rayon::ThreadPool>>,
+    chance_mode: ChanceMode,
+    eval_cache: LruCache<(u64, Player), f64>,
+    transpositions: LruCache<TranspositionKey, TranspositionEntry>,
+    tt_hits: u64,
+    enable_null_move: bool,
+    null_move_cutoffs: u64,
+    rules: GameRules,
+    seed: Option<u64>,
+    opponent_model: Option<OpponentModel>,
+    risk_profile: RiskProfile,
+    /// Boldness levels of both players' claims, overriding the risk profiles
+    claim_levels: Option<ClaimLevels>,
+    /// Claims bolder than this can only be challenged
+    accept_limit: Option<f64>,
+    /// How boldly the opponent is assumed to play, if they are assumed to
+    opponent_boldness: Option<BoldnessProfile>,
+    opening_book: Option<OpeningBook>,
+    confidence_weight: f64,
+    diversity_penalty: f64,
+    diversity_window: u8,
+    discount: f64,
+    /// Whether moves are sorted by a static score before being searched
+    move_ordering: bool,
+    /// Whether moves after the first are searched with a null window first
+    principal_variation_search: bool,
+    deadline: Option<Instant>,
+    /// Nodes the current iteration may still explore in a budgeted search
+    node_limit: Option<u64>,
+    aborted: bool,
+    root_depth: u8,
+    depth_policy: DepthPolicy,
+    /// Plies on the current line that the depth policy did not charge for
+    extensions: u8,
+    /// Least remaining depth of any node searched since the search began,
+    /// so `root_depth - min_remaining_depth` plies were actually reached
+    min_remaining_depth: u8,
+    parallel_probe: bool,
+    /// Whether the probe found the tree large enough to split, once it has run
+    probe_result: Option<bool>,
+    /// Triangular principal variation table, indexed by ply from the root
+    pv_table: Vec<PvLine>,
+    /// Up to two moves per ply that recently caused a cutoff, most recent first
+    killers: Vec<[Option<Move>; 2]>,
+    /// Cutoff score of each kind of move, kept until `reset_history`
+    history: HashMap<OrderingKey, u64>,
+    /// Zobrist hash and player to move of each position from the root down
+    /// to the node being searched, for detecting repetitions
+    line: Vec<(u64, Player)>,
+}
+
+/// Kind of move, as told apart by killer moves and the history heuristic
+type OrderingKey = (Action, Option<ClaimType>);
+
+/// Position, side to move, which side that is and how chance is folded
+type TranspositionKey = (u64, Player, bool, ChanceMode);
+
+/// What a stored value says about a node's true value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Bound {
+    Exact,
+    /// The search failed high: the true value is at least this
+    Lower,
+    /// The search failed low: the true value is at most this
+    Upper,
+}
+
+/// Result of searching a position, kept for when it comes up again
+#[derive(Debug, Clone)]
+struct TranspositionEntry {
+    value: f64,
+    depth: u8,
+    bound: Bound,
+    best_move: Option<Move>,
+}
+
+/// What a worker of the parallel root split found for its root move
+struct WorkerResult {
+    move_made: Move,
+    eval: f64,
+    line: PvLine,
+    nodes_explored: u64,
+    null_move_cutoffs: u64,
+    min_remaining_depth: u8,
+}
+
+/// Best line found below a node
+#[derive(Debug, Clone, Default)]
+struct PvLine {
+    moves: Vec<Move>,
+    /// Plies from the node to a terminal state along this line, if it ends in one
+    plies_to_terminal: Option<u8>,
 }
 
+/// Evaluation points added per unit of confidence above 0.5 at full weight
+const CONFIDENCE_SCALE: f64 = 20.0;
+
+/// Nodes explored between clock checks in a timed search
+const TIME_CHECK_INTERVAL: u64 = 1024;
+
+/// Nodes a depth-2 probe must explore for the parallel root split to be
+/// worth its overhead: about one full claim round
+const PARALLEL_PROBE_MIN_NODES: u64 = 32;
+
+/// Depth of the serial probe run before the first parallel search
+const PARALLEL_PROBE_DEPTH: u8 = 2;
+
+/// Width of the null window that principal variation search scouts with
+const PVS_WINDOW: f64 = 1e-6;
+
+/// Least remaining depth at which null-move pruning is tried
+const NULL_MOVE_MIN_DEPTH: u8 = 3;
+
+/// Extra depth taken off the reply to a null move
+const NULL_MOVE_REDUCTION: u8 = 2;
+
+/// Null moves are not tried with either player's trust within this of a
+/// win or loss threshold: one challenge swing
+const NULL_MOVE_TRUST_MARGIN: i32 = 15;
+
+/// Whether rayon can spawn threads here: not in the browser without extra
+/// setup, so parallel searches quietly run serially on wasm32
+const PARALLEL_AVAILABLE: bool = !cfg!(target_arch = "wasm32");
+
+/// Values at least this far from 0 are forced wins or losses
+const MATE_THRESHOLD: f64 = MATE_SCORE - 1_000.0;
+
+/// Regret matching rounds behind `optimal_bluff_rate`
+const BLUFF_RATE_ITERATIONS: usize = 2000;
+
+/// Most moves generated at any node: four boldness levels of four claim types
+const MAX_BRANCHING: usize = 16;
+
+/// Typical heap size of a generated claim description
+const DESCRIPTION_BYTES: usize = 40;
+
 impl AlphaBetaSearch {
     pub fn new(max_depth: u8, enable_parallel: bool) -> Self {
         Self {
-            evaluator: Evaluator::new(),
+            evaluator: Arc::new(Evaluator::new()),
             max_depth,
             nodes_explored: 0,
-            enable_parallel,
+            enable_parallel: enable_parallel && PARALLEL_AVAILABLE,
+            thread_pool: None,
+            chance_mode: ChanceMode::Sampled,
+            eval_cache: LruCache::new(0),
+            transpositions: LruCache::new(0),
+            tt_hits: 0,
+            enable_null_move: false,
+            null_move_cutoffs: 0,
+            rules: GameRules::default(),
+            seed: None,
+            opponent_model: None,
+            risk_profile: RiskProfile::Balanced,
+            claim_levels: None,
+            accept_limit: None,
+            opponent_boldness: None,
+            opening_book: None,
+            confidence_weight: 0.0,
+            diversity_penalty: 0.0,
+            diversity_window: 0,
+            discount: 1.0,
+            move_ordering: true,
+            principal_variation_search: true,
+            deadline: None,
+            node_limit: None,
+            aborted: false,
+            root_depth: 0,
+            depth_policy: DepthPolicy::Uniform,
+            extensions: 0,
+            min_remaining_depth: 0,
+            parallel_probe: false,
+            probe_result: None,
+            pv_table: Vec::new(),
+            killers: Vec::new(),
+            history: HashMap::new(),
+            line: Vec::new(),
         }
     }
 
+    /// Resolve challenges deterministically from `seed`, making repeated
+    /// searches of the same state explore exactly the same tree
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Score leaves with a custom evaluator, e.g. an `Evaluator` with a
+    /// different weight set or competitiveness, or any other `StateEvaluator`
+    pub fn with_evaluator(mut self, evaluator: impl StateEvaluator + 'static) -> Self {
+        self.evaluator = Arc::new(evaluator);
+        self
+    }
+
+    /// Search under a custom rule set instead of the standard thresholds
+    pub fn with_rules(mut self, rules: GameRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Let both players claim at `levels` only, as
+    /// `GameTree::set_claim_levels` does
+    pub fn with_claim_levels(mut self, levels: ClaimLevels) -> Self {
+        self.claim_levels = Some(levels);
+        self
+    }
+
+    /// Never accept a claim bolder than `limit`, as
+    /// `GameTree::set_accept_limit` does
+    pub fn with_accept_limit(mut self, limit: f64) -> Self {
+        self.accept_limit = Some(limit);
+        self
+    }
+
+    /// Remember up to `capacity` searched positions, evicting the least
+    /// recently used when full, and reuse their values when a position
+    /// comes up again at no greater depth. Positions are keyed by their
+    /// Zobrist hash, which ignores the move history, so different
+    /// move orders reaching the same position share an entry; values of
+    /// history-dependent evaluation terms such as momentum come from
+    /// whichever line was searched first. The table lives as long as this
+    /// search, is not consulted at the root or while discounting, and is
+    /// not shared with workers of the parallel root split.
+    pub fn with_transposition_table(mut self, capacity: usize) -> Self {
+        self.transpositions = LruCache::new(capacity);
+        self
+    }
+
+    /// Have the opponent answer claims as `model` predicts instead of with
+    /// the reply that is worst for us. Their challenge is then a weighted
+    /// mix of challenging and accepting, which models opponents who cannot
+    /// always tell a bluff from an honest claim.
+    pub fn with_opponent_model(mut self, model: OpponentModel) -> Self {
+        self.opponent_model = Some(model);
+        self
+    }
+
+    /// Assume the opponent only plays the moves of `profile`: wherever they
+    /// are to move, the search leaves out moves the profile would not play.
+    /// Unlike `with_opponent_model`, which mixes their replies to a claim,
+    /// this narrows which moves they are searched with at all.
+    pub fn with_opponent_boldness(mut self, profile: BoldnessProfile) -> Self {
+        self.opponent_boldness = Some(profile);
+        self
+    }
+
+    /// Play with `profile`'s appetite for risk: the searching player's
+    /// claims are generated at its boldness levels and its contempt is
+    /// added to every evaluation
+    pub fn with_risk_profile(mut self, profile: RiskProfile) -> Self {
+        self.risk_profile = profile;
+        self
+    }
+
+    /// Spend search depth per `policy`, e.g. to extend the search through
+    /// claims so their answers are always seen
+    pub fn with_depth_policy(mut self, policy: DepthPolicy) -> Self {
+        self.depth_policy = policy;
+        self
+    }
+
+    /// Play moves from `book` without searching in the positions it covers.
+    /// Consulted by `search`, `search_timed` and `search_node_budgeted`;
+    /// positions the book does not cover are searched as usual.
+    pub fn with_opening_book(mut self, book: OpeningBook) -> Self {
+        self.opening_book = Some(book);
+        self
+    }
+
+    /// Run parallel searches in a dedicated pool of `threads` threads
+    /// rather than the global rayon pool, to bound the CPU one search can
+    /// use, e.g. per request in a server. 0 keeps the global pool, as does
+    /// a pool that cannot be created. Has no effect on serial searches.
+    pub fn with_thread_pool(mut self, threads: usize) -> Self {
+        self.thread_pool = match threads {
+            0 => None,
+            _ => rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .thread_name(|i| format!("optimizer-search-{}", i))
+                .build()
+                .ok()
+                .map(Arc::new),
+        };
+        self
+    }
+
+    /// Cache up to `capacity` leaf evaluations, evicting the least recently
+    /// used entry when full. A capacity of 0 (the default) disables caching.
+    /// The cache lives as long as this search and is shared by later calls;
+    /// workers spawned for the parallel root split evaluate uncached.
+    pub fn with_cache_capacity(mut self, capacity: usize) -> Self {
+        self.eval_cache = LruCache::new(capacity);
+        self
+    }
+
+    pub fn rules(&self) -> &GameRules {
+        &self.rules
+    }
+
+    /// Rough upper bound on the memory a search with this configuration uses:
+    /// the evaluation cache at full capacity plus per-thread scratch space.
+    ///
+    /// The search is depth-first and keeps no tree in memory, so scratch
+    /// space grows with `max_depth` rather than with the size of the tree:
+    /// each ply holds a state with its history, the generated moves and a
+    /// principal variation line. Parallel searches need that once per
+    /// thread of their pool: the one from `with_thread_pool`, or else the
+    /// current rayon pool.
+    pub fn estimated_memory_bytes(&self) -> usize {
+        let depth = self.max_depth as usize + 1;
+        // Moves carry a claim whose description lives on the heap
+        let move_bytes = std::mem::size_of::<Move>() + DESCRIPTION_BYTES;
+
+        let state_bytes = std::mem::size_of::<GameState>() + depth * move_bytes;
+        let moves_bytes = MAX_BRANCHING * move_bytes;
+        let pv_bytes = std::mem::size_of::<PvLine>() + depth * move_bytes;
+        let per_thread = depth * (state_bytes + moves_bytes + pv_bytes);
+
+        let threads = match (&self.thread_pool, self.enable_parallel) {
+            (_, false) => 1,
+            (Some(pool), true) => pool.current_num_threads(),
+            (None, true) => rayon::current_num_threads(),
+        };
+
+        self.eval_cache.estimated_bytes() + self.transpositions.estimated_bytes() + threads * per_thread
+    }
+
+    /// Hits and misses accumulated since construction, plus current occupancy
+    pub fn cache_stats(&self) -> CacheStats {
+        self.eval_cache.stats()
+    }
+
     pub fn search(&mut self, state: &GameState, player: Player) -> SearchResult {
+        if let Some(result) = self.book_result(state, player) {
+            return result;
+        }
+
+        if self.parallel_probe && self.enable_parallel && self.probe_result.is_none() {
+            let probe = self.worker(PARALLEL_PROBE_DEPTH).search(state, player);
+            self.probe_result = Some(probe.nodes_explored >= PARALLEL_PROBE_MIN_NODES);
+        }
+
+        self.search_to_depth(state, player, self.max_depth)
+    }
+
+    /// Iterative deepening from depth 1 up to `max_depth`, stopping once
+    /// `budget` has elapsed.
+    ///
+    /// The clock is checked every 1024 nodes inside the recursion, so an
+    /// iteration that runs past the deadline is abandoned immediately rather
+    /// than finished. The result comes from the deepest completed iteration;
+    /// if not even depth 1 completed, the best root move evaluated so far is
+    /// returned. `depth_reached` is the deepest completed depth and
+    /// `nodes_explored` counts every iteration, the abandoned one included.
+    /// Timed searches always run serially.
+    pub fn search_timed(&mut self, state: &GameState, player: Player, budget: Duration) -> SearchResult {
         let start_time = Instant::now();
-        self.nodes_explored = 0;
+        self.deadline = Some(start_time + budget);
 
-        let tree = GameTree::new(state.clone());
-        
-        let (best_move, evaluation) = if self.enable_parallel && self.max_depth > 3 {
-            self.parallel_alpha_beta(&tree, state, self.max_depth, player)
-        } else {
-            self.alpha_beta(
+        let result = self.iterative_deepening(state, player, start_time, None);
+        self.deadline = None;
+        result
+    }
+
+    /// Iterative deepening from depth 1 up to `max_depth`, stopping once
+    /// `node_budget` nodes have been explored across all iterations.
+    ///
+    /// Unlike a time budget, this is machine-independent: with a seed, the
+    /// same budget always explores the same nodes and returns the same
+    /// result, which makes it suitable for fair, reproducible comparisons
+    /// between configurations. The iteration that would exceed the budget
+    /// is abandoned at that node, so `nodes_explored` never exceeds it.
+    /// Otherwise the result is reported as for `search_timed`.
+    pub fn search_node_budgeted(&mut self, state: &GameState, player: Player, node_budget: u64) -> SearchResult {
+        self.iterative_deepening(state, player, Instant::now(), Some(node_budget))
+    }
+
+    /// Deepen until `max_depth`, an abort from the deadline, or `node_budget`
+    fn iterative_deepening(
+        &mut self,
+        state: &GameState,
+        player: Player,
+        start_time: Instant,
+        node_budget: Option<u64>,
+    ) -> SearchResult {
+        if let Some(result) = self.book_result(state, player) {
+            return result;
+        }
+
+        let _span = instrument::search_started(player, self.max_depth);
+        let tree = self.build_tree(state, player);
+        self.aborted = false;
+        self.tt_hits = 0;
+        self.null_move_cutoffs = 0;
+
+        let mut total_nodes = 0;
+        let mut completed: Option<(Move, f64, u8)> = None;
+        let mut completed_line = Vec::new();
+        let mut partial: Option<(Move, f64)> = None;
+        // Value of a root without moves, such as a finished game
+        let mut root_value = None;
+        let mut root = state.clone();
+        self.killers.clear();
+
+        for depth in 1..=self.max_depth {
+            self.nodes_explored = 0;
+            self.node_limit = node_budget.map(|budget| budget - total_nodes);
+            self.begin_iteration(depth);
+            let (best_move, evaluation) = self.alpha_beta(
                 &tree,
-                state,
-                self.max_depth,
+                &mut root,
+                depth,
                 f64::NEG_INFINITY,
                 f64::INFINITY,
                 player,
                 true,
-            )
-        };
+            );
+            total_nodes += self.nodes_explored;
+
+            if self.aborted {
+                partial = best_move.map(|m| (m, evaluation));
+                break;
+            }
+
+            instrument::depth_completed(depth, total_nodes, evaluation, self.tt_hits, self.null_move_cutoffs);
+            match best_move {
+                Some(m) => {
+                    completed_line = self.principal_variation(&m);
+                    completed = Some((m, evaluation, self.depth_reached()));
+                }
+                // No moves at the root: deeper iterations cannot change anything
+                None => {
+                    root_value = Some(evaluation);
+                    break;
+                }
+            }
+
+            let timed_out = self.deadline.is_some_and(|deadline| Instant::now() >= deadline);
+            let spent = node_budget.is_some_and(|budget| total_nodes >= budget);
+            if timed_out || spent {
+                break;
+            }
+        }
+
+        self.node_limit = None;
+        self.aborted = false;
+        self.nodes_explored = total_nodes;
 
+        let (best_move, evaluation, depth_reached) = match (completed, partial) {
+            (Some((m, eval, depth)), _) => (m, eval, depth),
+            (None, Some((m, eval))) => (m, eval, 0),
+            (None, None) => (
+                self.default_move(state, player),
+                root_value.unwrap_or_else(|| self.evaluator.evaluate(state, player)),
+                0,
+            ),
+        };
+        // A line from an abandoned iteration is not worth showing
+        if depth_reached == 0 {
+            completed_line = vec![best_move.clone()];
+        }
         let time_ms = start_time.elapsed().as_millis() as u64;
+        instrument::search_finished(depth_reached, total_nodes, evaluation, time_ms);
 
         SearchResult {
-            best_move: best_move.map(|m| crate::MoveResult {
-                action: format!("{:?}", m.action),
-                confidence: m.confidence,
-            }),
+            best_move,
             evaluation,
-            nodes_explored: self.nodes_explored,
-            depth_reached: self.max_depth,
+            nodes_explored: total_nodes,
+            depth_reached,
             time_ms,
+            tt_hits: self.tt_hits,
+            null_move_cutoffs: self.null_move_cutoffs,
+            principal_variation: completed_line,
         }
     }
 
-    fn alpha_beta(
+    /// Shrink `state` to a simpler one whose search result still satisfies
+    /// `predicate`, for turning a surprising search into a minimal
+    /// reproducer. Repeatedly tries dropping move history and moving each
+    /// player's trust toward 50, keeping any step after which a search of
+    /// `player` to `depth` still satisfies the predicate, until no step
+    /// does. Searches should be seeded to make the result reproducible.
+    /// If `state` itself fails the predicate it is returned unchanged.
+    pub fn minimize_state(
         &mut self,
-        tree: &GameTree,
         state: &GameState,
-        depth: u8,
-        mut alpha: f64,
-        mut beta: f64,
+        predicate: impl Fn(&SearchResult) -> bool,
         player: Player,
-        is_maximizing: bool,
-    ) -> (Option<Move>, f64) {
-        self.nodes_explored += 1;
+        depth: u8,
+    ) -> GameState {
+        let mut current = state.clone();
+        if !predicate(&self.search_to_depth(&current, player, depth)) {
+            return current;
+        }
 
-        // Terminal conditions
-        if depth == 0 || tree.is_terminal(state) {
-            let eval = self.evaluator.evaluate(state, player);
-            return (None, eval);
+        'shrink: loop {
+            for candidate in shrink_candidates(&current) {
+                if predicate(&self.search_to_depth(&candidate, player, depth)) {
+                    current = candidate;
+                    continue 'shrink;
+                }
+            }
+            return current;
         }
+    }
 
-        let moves = tree.generate_moves(state, player);
+    /// Search `state` to `depth` and bundle the result with its state key
+    /// and depth, for storing in an analysis cache
+    pub fn analyze(&mut self, state: &GameState, player: Player, depth: u8) -> CachedAnalysis {
+        let result = self.search_to_depth(state, player, depth);
+        CachedAnalysis::new(state, player, depth, result)
+    }
 
-        if moves.is_empty() {
-            let eval = self.evaluator.evaluate(state, player);
-            return (None, eval);
+    /// Search assuming every challenge resolves as badly as possible for `player`.
+    /// Picks the move whose worst chance outcome is best, rather than the one
+    /// with the best average.
+    pub fn search_maximin_chance(&mut self, state: &GameState, player: Player, depth: u8) -> SearchResult {
+        self.search_with_chance_mode(state, player, depth, ChanceMode::WorstCase)
+    }
+
+    /// Backed-up value of every root move, each searched with a full window
+    pub fn search_scored(&mut self, state: &GameState, player: Player, depth: u8) -> Vec<(Move, f64)> {
+        self.nodes_explored = 0;
+
+        let tree = self.build_tree(state, player);
+        self.scored_root_moves(&tree, state, player, depth, |_, _| {})
+    }
+
+    /// The `n` best root moves, best first, each as a result of its own:
+    /// its value, its line of play and the nodes spent on it. Every root
+    /// move is searched to `max_depth - 1` below it with a full window, so
+    /// the values are exact and comparable, while each subtree is still
+    /// pruned by alpha-beta. Moves of equal value keep their generation
+    /// order, so repeated calls rank ties the same way. Empty if there is
+    /// nothing to play, as in a finished game.
+    pub fn search_multipv(&mut self, state: &GameState, player: Player, n: usize) -> Vec<SearchResult> {
+        self.nodes_explored = 0;
+        self.tt_hits = 0;
+        self.null_move_cutoffs = 0;
+
+        let tree = self.build_tree(state, player);
+        let depth = self.max_depth.max(1);
+        self.begin_search(&tree, state, depth);
+        self.line.push((state.zobrist_hash(), player));
+        if tree.outcome(state).is_some() || tree.is_draw(state, player, &self.line) {
+            return Vec::new();
         }
+        let mut root = state.clone();
 
-        if is_maximizing {
-            let mut max_eval = f64::NEG_INFINITY;
-            let mut best_move = None;
+        let mut candidates: Vec<SearchResult> = tree
+            .generate_moves(state, player)
+            .into_iter()
+            .map(|move_candidate| {
+                let start_time = Instant::now();
+                let nodes_before = self.nodes_explored;
+                self.min_remaining_depth = depth;
+                self.pv_table[1] = PvLine::default();
 
-            for move_candidate in moves {
-                let new_state = tree.apply_move(state, &move_candidate);
-                let (_, eval) = self.alpha_beta(
-                    tree,
-                    &new_state,
-                    depth - 1,
-                    alpha,
-                    beta,
-                    player.opponent(),
-                    false,
+                let evaluation = self.search_move(
+                    &tree,
+                    &mut root,
+                    &move_candidate,
+                    depth,
+                    f64::NEG_INFINITY,
+                    f64::INFINITY,
+                    player,
+                    true,
                 );
 
-                if eval > max_eval {
-                    max_eval = eval;
-                    best_move = Some(move_candidate);
+                let mut line = vec![move_candidate.clone()];
+                line.extend(std::mem::take(&mut self.pv_table[1]).moves);
+
+                SearchResult {
+                    best_move: move_candidate,
+                    evaluation,
+                    nodes_explored: self.nodes_explored - nodes_before,
+                    depth_reached: self.depth_reached(),
+                    time_ms: start_time.elapsed().as_millis() as u64,
+                    tt_hits: 0,
+                    null_move_cutoffs: 0,
+                    principal_variation: line,
                 }
+            })
+            .collect();
 
-                alpha = alpha.max(eval);
+        // Stable, so ties stay in generation order
+        candidates.sort_by(|a, b| b.evaluation.total_cmp(&a.evaluation));
+        candidates.truncate(n);
+        candidates
+    }
 
-                // Beta cutoff
-                if beta <= alpha {
-                    break;
-                }
-            }
+    /// Search every root move with a full window, as `search_scored` does,
+    /// sending each move and its value on `tx` as soon as it is known so a
+    /// UI can show partial analysis. Returns the best of them once all are
+    /// done. The search carries on if the receiver hangs up.
+    pub fn search_streaming(
+        &mut self,
+        state: &GameState,
+        player: Player,
+        depth: u8,
+        tx: Sender<(Move, f64)>,
+    ) -> SearchResult {
+        let start_time = Instant::now();
+        self.nodes_explored = 0;
+        self.tt_hits = 0;
+        self.null_move_cutoffs = 0;
+
+        let tree = self.build_tree(state, player);
+        let scored = self.scored_root_moves(&tree, state, player, depth, |m, eval| {
+            let _ = tx.send((m.clone(), eval));
+        });
+
+        let best = scored.into_iter().fold(None, |best: Option<(Move, f64)>, (m, eval)| match best {
+            Some((_, best_eval)) if best_eval >= eval => best,
+            _ => Some((m, eval)),
+        });
+        let (best_move, evaluation) = match best {
+            Some(best) => best,
+            None => (self.default_move(state, player), self.evaluator.evaluate(state, player)),
+        };
 
-            (best_move, max_eval)
+        SearchResult {
+            principal_variation: vec![best_move.clone()],
+            best_move,
+            evaluation,
+            nodes_explored: self.nodes_explored,
+            depth_reached: self.depth_reached(),
+            time_ms: start_time.elapsed().as_millis() as u64,
+            tt_hits: self.tt_hits,
+            null_move_cutoffs: self.null_move_cutoffs,
+        }
+    }
+
+    /// Number of plies until the game ends along the principal variation,
+    /// or `None` if the line found within `max_depth` does not reach a
+    /// terminal state. A state that is already over reports `Some(0)`.
+    pub fn plies_to_terminal(&mut self, state: &GameState, player: Player, max_depth: u8) -> Option<u8> {
+        self.nodes_explored = 0;
+
+        let tree = self.build_tree(state, player);
+        self.begin_search(&tree, state, max_depth);
+        self.alpha_beta(
+            &tree,
+            &mut state.clone(),
+            max_depth,
+            f64::NEG_INFINITY,
+            f64::INFINITY,
+            player,
+            true,
+        );
+
+        self.pv_table[0].plies_to_terminal
+    }
+
+    /// How much the decision at `state` matters: the spread between the best
+    /// and worst root move values. Near zero when every move is about as good;
+    /// large when a wrong choice is costly.
+    pub fn decision_criticality(&mut self, state: &GameState, player: Player, depth: u8) -> f64 {
+        let scored = self.search_scored(state, player, depth);
+
+        let best = scored.iter().map(|(_, eval)| *eval).fold(f64::NEG_INFINITY, f64::max);
+        let worst = scored.iter().map(|(_, eval)| *eval).fold(f64::INFINITY, f64::min);
+
+        if scored.is_empty() {
+            0.0
         } else {
-            let mut min_eval = f64::INFINITY;
-            let mut best_move = None;
+            best - worst
+        }
+    }
 
-            for move_candidate in moves {
-                let new_state = tree.apply_move(state, &move_candidate);
-                let (_, eval) = self.alpha_beta(
-                    tree,
-                    &new_state,
-                    depth - 1,
-                    alpha,
-                    beta,
-                    player.opponent(),
-                    true,
-                );
+    /// Approximate the mixed-strategy equilibrium over the root moves.
+    ///
+    /// Builds a payoff matrix of each root move against each opponent reply,
+    /// scored by searching the remaining `depth - 2` plies, and runs
+    /// `iterations` rounds of regret matching on it. Moves the opponent cannot
+    /// answer contribute their backed-up value to every column. Returns the
+    /// probability of playing each root move.
+    pub fn equilibrium_strategy(
+        &mut self,
+        state: &GameState,
+        player: Player,
+        depth: u8,
+        iterations: usize,
+    ) -> Vec<(Move, f64)> {
+        self.nodes_explored = 0;
 
-                if eval < min_eval {
-                    min_eval = eval;
-                    best_move = Some(move_candidate);
-                }
+        let tree = self.build_tree(state, player);
+        self.begin_search(&tree, state, depth);
+        let moves = tree.generate_moves(state, player);
+        let mut rows: Vec<Vec<f64>> = Vec::with_capacity(moves.len());
 
-                beta = beta.min(eval);
+        for move_candidate in &moves {
+            let (_, row) = self.payoff_row(&tree, state, move_candidate, player, depth);
+            rows.push(row);
+        }
 
-                // Alpha cutoff
-                if beta <= alpha {
-                    break;
+        // Pad unanswerable rows so every row has one entry per column
+        let columns = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        for row in &mut rows {
+            let fill = row.iter().cloned().fold(f64::INFINITY, f64::min);
+            row.resize(columns, fill);
+        }
+
+        moves.into_iter().zip(regret_matching(&rows, iterations)).collect()
+    }
+
+    /// How often to bluff in the recommended mix of root claims.
+    ///
+    /// Each root claim is scored against each opponent reply as in
+    /// `equilibrium_strategy`, with challenges valued at their expected
+    /// outcome. With the opponent model's bluff detection skill (none
+    /// without a model) the opponent sees through the claim: a bluff is
+    /// challenged and exposed, an honest claim accepted. Otherwise they
+    /// answer as best they can without knowing. Returns the total
+    /// probability of the bluffing claims in the equilibrium mix over those
+    /// payoffs, or 0 if there are no claims to make.
+    pub fn optimal_bluff_rate(&mut self, state: &GameState, player: Player, depth: u8) -> f64 {
+        self.nodes_explored = 0;
+        let previous = std::mem::replace(&mut self.chance_mode, ChanceMode::Expected);
+
+        let tree = self.build_tree(state, player);
+        self.begin_search(&tree, state, depth);
+        let claims: Vec<Move> = tree
+            .generate_moves(state, player)
+            .into_iter()
+            .filter(|m| m.claim.is_some())
+            .collect();
+        let skill = self.opponent_model.map_or(0.0, |model| model.bluff_detection_skill());
+
+        let mut rows: Vec<Vec<f64>> = Vec::with_capacity(claims.len());
+        for claim_move in &claims {
+            let (replies, mut row) = self.payoff_row(&tree, state, claim_move, player, depth);
+
+            let is_bluff = claim_move.claim.as_ref().is_some_and(|claim| claim.is_bluff);
+            if let Some(detected) = self.seen_through_value(&tree, state, claim_move, &replies, is_bluff, player, depth) {
+                for value in &mut row {
+                    *value = skill * detected + (1.0 - skill) * *value;
                 }
             }
 
-            (best_move, min_eval)
+            rows.push(row);
         }
+        self.chance_mode = previous;
+
+        let columns = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        for row in &mut rows {
+            let fill = row.iter().cloned().fold(f64::INFINITY, f64::min);
+            row.resize(columns, fill);
+        }
+
+        claims
+            .iter()
+            .zip(regret_matching(&rows, BLUFF_RATE_ITERATIONS))
+            .filter(|(m, _)| m.claim.as_ref().is_some_and(|claim| claim.is_bluff))
+            .map(|(_, probability)| probability)
+            .sum()
     }
 
-    fn parallel_alpha_beta(
+    /// Value of `claim_move` once the opponent has seen through it: an
+    /// honest claim is accepted and a bluff is challenged and refuted
+    #[allow(clippy::too_many_arguments)]
+    fn seen_through_value(
         &mut self,
         tree: &GameTree,
         state: &GameState,
+        claim_move: &Move,
+        replies: &[Move],
+        is_bluff: bool,
+        player: Player,
         depth: u8,
+    ) -> Option<f64> {
+        let child = tree.resolution_outcomes(state, claim_move).swap_remove(0).0;
+        let action = if is_bluff { Action::Challenge } else { Action::Accept };
+        let reply = replies.iter().find(|reply| reply.action == action)?;
+
+        let mut outcomes = tree.resolution_outcomes(&child, reply);
+        // Outcomes run upheld, refuted, then any inconclusive one
+        let mut resolved = if is_bluff && outcomes.len() > 1 {
+            outcomes.swap_remove(1).0
+        } else {
+            outcomes.swap_remove(0).0
+        };
+
+        Some(self.alpha_beta(tree, &mut resolved, depth - 2, f64::NEG_INFINITY, f64::INFINITY, player, true).1)
+    }
+
+    /// Value of a root move against each opponent reply, searching the
+    /// remaining `depth - 2` plies, along with those replies. A move the
+    /// opponent cannot answer gets its backed-up value as the only entry.
+    fn payoff_row(
+        &mut self,
+        tree: &GameTree,
+        state: &GameState,
+        move_candidate: &Move,
         player: Player,
-    ) -> (Option<Move>, f64) {
-        let moves = tree.generate_moves(state, player);
+        depth: u8,
+    ) -> (Vec<Move>, Vec<f64>) {
+        let mut outcomes = tree.resolution_outcomes(state, move_candidate);
+        let replies = match outcomes.as_slice() {
+            [(child, _)] if depth >= 2 && !tree.is_terminal(child) => {
+                tree.generate_moves(child, player.opponent())
+            }
+            _ => Vec::new(),
+        };
 
-        if moves.is_empty() {
-            let eval = self.evaluator.evaluate(state, player);
-            return (None, eval);
+        if replies.is_empty() {
+            let value = self.child_value(
+                tree,
+                &mut state.clone(),
+                move_candidate,
+                depth.saturating_sub(1),
+                f64::NEG_INFINITY,
+                f64::INFINITY,
+                player,
+                true,
+            );
+            return (replies, vec![value]);
         }
 
-        // Evaluate root moves in parallel
-        let results: Vec<(Move, f64)> = moves
-            .par_iter()
-            .map(|move_candidate| {
-                let new_state = tree.apply_move(state, move_candidate);
-                let mut local_search = AlphaBetaSearch::new(depth - 1, false);
-                let (_, eval) = local_search.alpha_beta(
+        let mut child = outcomes.swap_remove(0).0;
+        let row = replies
+            .iter()
+            .map(|reply| {
+                -self.child_value(
                     tree,
-                    &new_state,
-                    depth - 1,
+                    &mut child,
+                    reply,
+                    depth - 2,
                     f64::NEG_INFINITY,
                     f64::INFINITY,
                     player.opponent(),
                     false,
-                );
-                (move_candidate.clone(), eval)
+                )
             })
             .collect();
 
-        // Find best result
-        let (best_move, best_eval) = results
-            .into_iter()
-            .max_by(|(_, eval1), (_, eval2)| {
-                eval1.partial_cmp(eval2).unwrap_or(std::cmp::Ordering::Equal)
-            })
-            .unwrap();
-
-        (Some(best_move), best_eval)
+        (replies, row)
     }
 
-    fn default_move(&self, _state: &GameState, player: Player) -> Move {
-        Move {
-            action: crate::Action::Accept,
-            player,
-            claim: None,
-            confidence: 0.5,
-        }
+    fn search_with_chance_mode(
+        &mut self,
+        state: &GameState,
+        player: Player,
+        depth: u8,
+        mode: ChanceMode,
+    ) -> SearchResult {
+        let previous = std::mem::replace(&mut self.chance_mode, mode);
+        let result = self.search_to_depth(state, player, depth);
+        self.chance_mode = previous;
+        result
     }
 
-    pub fn set_parallel(&mut self, enable: bool) {
-        self.enable_parallel = enable;
-    }
-}
+    fn search_to_depth(&mut self, state: &GameState, player: Player, depth: u8) -> SearchResult {
+        let _span = instrument::search_started(player, depth);
+        let start_time = Instant::now();
+        self.nodes_explored = 0;
+        self.tt_hits = 0;
+        self.null_move_cutoffs = 0;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::Phase;
+        let tree = self.build_tree(state, player);
+        self.begin_search(&tree, state, depth);
 
-    fn create_test_state() -> GameState {
-        GameState {
-            round: 1,
-            phase: Phase::Claim,
+        let adjusted = self.confidence_weight != 0.0 || self.diversity_penalty != 0.0;
+        let (best_move, evaluation) = if adjusted && depth > 0 {
+            self.select_with_adjustments(&tree, state, depth, player)
+        } else if self.enable_parallel && self.probe_result != Some(false) && depth > 3 {
+            match self.thread_pool.clone() {
+                Some(pool) => pool.install(|| self.parallel_alpha_beta(&tree, state, depth, player, true)),
+                None => self.parallel_alpha_beta(&tree, state, depth, player, true),
+            }
+        } else {
+            self.alpha_beta(
+                &tree,
+                &mut state.clone(),
+                depth,
+                f64::NEG_INFINITY,
+                f64::INFINITY,
+                player,
+                true,
+            )
+        };
+
+        let time_ms = start_time.elapsed().as_millis() as u64;
+        let best_move = best_move.unwrap_or_else(|| self.default_move(state, player));
+        instrument::depth_completed(depth, self.nodes_explored, evaluation, self.tt_hits, self.null_move_cutoffs);
+        instrument::search_finished(self.depth_reached(), self.nodes_explored, evaluation, time_ms);
+
+        SearchResult {
+            principal_variation: self.principal_variation(&best_move),
+            best_move,
+            evaluation,
+            nodes_explored: self.nodes_explored,
+            depth_reached: self.depth_reached(),
+            time_ms,
+            tt_hits: self.tt_hits,
+            null_move_cutoffs: self.null_move_cutoffs,
+        }
+    }
+
+    /// Line of play the last search expects, starting with `best_move`.
+    /// Root selection that bypasses the recursion, such as the play-style
+    /// adjustments, leaves no line behind, and then only the move itself
+    /// is known.
+    fn principal_variation(&self, best_move: &Move) -> Vec<Move> {
+        match self.pv_table.first() {
+            Some(line) if line.moves.first() == Some(best_move) => line.moves.clone(),
+            _ => vec![best_move.clone()],
+        }
+    }
+
+    /// Value of every root move, each reported to `on_scored` as it completes
+    fn scored_root_moves(
+        &mut self,
+        tree: &GameTree,
+        state: &GameState,
+        player: Player,
+        depth: u8,
+        mut on_scored: impl FnMut(&Move, f64),
+    ) -> Vec<(Move, f64)> {
+        self.begin_search(tree, state, depth);
+        self.line.push((state.zobrist_hash(), player));
+        let moves = tree.generate_moves(state, player);
+        let mut root = state.clone();
+
+        let scored = moves
+            .into_iter()
+            .map(|move_candidate| {
+                let eval = self.search_move(
+                    tree,
+                    &mut root,
+                    &move_candidate,
+                    depth,
+                    f64::NEG_INFINITY,
+                    f64::INFINITY,
+                    player,
+                    true,
+                );
+                on_scored(&move_candidate, eval);
+                (move_candidate, eval)
+            })
+            .collect();
+
+        self.line.pop();
+        scored
+    }
+
+    /// Pick the root move with the best evaluation after the play-style
+    /// adjustments: the confidence bonus and the claim diversity penalty.
+    /// The reported evaluation is the move's unadjusted backed-up value.
+    fn select_with_adjustments(
+        &mut self,
+        tree: &GameTree,
+        state: &GameState,
+        depth: u8,
+        player: Player,
+    ) -> (Option<Move>, f64) {
+        self.nodes_explored += 1;
+
+        let weight = self.confidence_weight;
+        let bonus = |m: &Move| weight * (m.confidence - 0.5) * CONFIDENCE_SCALE;
+
+        // Claim types this player used in their last `diversity_window` moves
+        let recent_types: Vec<_> = state
+            .move_history
+            .iter()
+            .rev()
+            .filter(|m| m.player == player)
+            .take(self.diversity_window as usize)
+            .filter_map(|m| m.claim.as_ref().map(|claim| claim.claim_type))
+            .collect();
+        let penalty = self.diversity_penalty;
+        let repetition = |m: &Move| match &m.claim {
+            Some(claim) if recent_types.contains(&claim.claim_type) => penalty,
+            _ => 0.0,
+        };
+
+        let scored = self.scored_root_moves(tree, state, player, depth, |_, _| {});
+        let best = scored.into_iter().fold(None, |best: Option<(Move, f64, f64)>, (m, eval)| {
+            let score = eval + bonus(&m) - repetition(&m);
+            match best {
+                Some((_, _, best_score)) if best_score >= score => best,
+                _ => Some((m, eval, score)),
+            }
+        });
+
+        match best {
+            Some((m, eval, _)) => (Some(m), eval),
+            None => (None, self.evaluator.evaluate(state, player)),
+        }
+    }
+
+    /// Prepare per-search state for a search of `state` rooted at `depth`.
+    /// The line starts with the positions of the game so far, so the
+    /// search sees repetitions that began before it did.
+    fn begin_search(&mut self, tree: &GameTree, state: &GameState, depth: u8) {
+        self.killers.clear();
+        self.line = tree.history_positions(state);
+        self.begin_iteration(depth);
+    }
+
+    /// Like `begin_search`, but keeps the killer moves of earlier iterations
+    fn begin_iteration(&mut self, depth: u8) {
+        self.root_depth = depth;
+        self.extensions = 0;
+        self.min_remaining_depth = depth;
+        self.pv_table.clear();
+        self.pv_table.resize(depth as usize + 2, PvLine::default());
+    }
+
+    /// Plies below the root the current search actually reached, which is
+    /// less than the depth searched to when every line ends early in a
+    /// finished game
+    fn depth_reached(&self) -> u8 {
+        self.root_depth - self.min_remaining_depth
+    }
+
+    /// Ply of a node searched with `depth` remaining, with its PV line cleared
+    fn enter_ply(&mut self, depth: u8) -> usize {
+        let ply = self.root_depth.saturating_add(self.extensions).saturating_sub(depth) as usize;
+        if self.pv_table.len() < ply + 2 {
+            self.pv_table.resize(ply + 2, PvLine::default());
+        }
+        self.pv_table[ply] = PvLine::default();
+        ply
+    }
+
+    /// Make `best` followed by the child's line the PV of the node at `ply`
+    fn update_pv(&mut self, ply: usize, best: &Move) {
+        let child = std::mem::take(&mut self.pv_table[ply + 1]);
+        let line = &mut self.pv_table[ply];
+
+        line.moves.clear();
+        line.moves.push(best.clone());
+        line.moves.extend(child.moves);
+        line.plies_to_terminal = child.plies_to_terminal.map(|plies| plies.saturating_add(1));
+    }
+
+    /// The opening book's move for `state`, if it has one, with the state's
+    /// static evaluation since nothing was searched
+    fn book_result(&self, state: &GameState, player: Player) -> Option<SearchResult> {
+        let best_move = self.opening_book.as_ref()?.lookup(state, player)?.clone();
+
+        Some(SearchResult {
+            principal_variation: vec![best_move.clone()],
+            best_move,
+            evaluation: self.evaluator.evaluate(state, player),
+            nodes_explored: 0,
+            depth_reached: 0,
+            time_ms: 0,
+            tt_hits: 0,
+            null_move_cutoffs: 0,
+        })
+    }
+
+    /// Tree for a search of `state` by `player`
+    fn build_tree(&self, state: &GameState, player: Player) -> GameTree {
+        let mut tree = GameTree::new_with_rules(state.clone(), self.rules);
+        if let Some(seed) = self.seed {
+            tree.set_seed(seed);
+        }
+        tree.set_risk_profile(player, self.risk_profile);
+        if let Some(levels) = &self.claim_levels {
+            tree.set_claim_levels(levels.clone());
+        }
+        tree.set_accept_limit(self.accept_limit);
+        tree
+    }
+
+    /// Search a node with `state` on the current line for as long as it
+    /// is being searched. The search is negamax: `player` is to move, the
+    /// window and the value returned are from their point of view, and
+    /// `root_side` says whether they are the searching player.
+    #[allow(clippy::too_many_arguments)]
+    fn alpha_beta(
+        &mut self,
+        tree: &GameTree,
+        state: &mut GameState,
+        depth: u8,
+        alpha: f64,
+        beta: f64,
+        player: Player,
+        root_side: bool,
+    ) -> (Option<Move>, f64) {
+        self.line.push((state.zobrist_hash(), player));
+        let result = self.search_node(tree, state, depth, alpha, beta, player, root_side);
+        self.line.pop();
+        result
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn search_node(
+        &mut self,
+        tree: &GameTree,
+        state: &mut GameState,
+        depth: u8,
+        mut alpha: f64,
+        mut beta: f64,
+        player: Player,
+        root_side: bool,
+    ) -> (Option<Move>, f64) {
+        if self.out_of_time() {
+            return (None, 0.0);
+        }
+
+        self.nodes_explored += 1;
+        self.min_remaining_depth = self.min_remaining_depth.min(depth);
+
+        let ply = self.enter_ply(depth);
+
+        // Leaves are always scored from the searching player's point of
+        // view, so an evaluator need not be symmetric between the players,
+        // and negated at the opponent's nodes
+        let perspective = if root_side { player } else { player.opponent() };
+        let sign = side_sign(root_side);
+
+        // Terminal conditions
+        match tree.outcome(state) {
+            // A drawn game is worth nothing to either side
+            Some(GameOutcome::Draw) => {
+                self.pv_table[ply].plies_to_terminal = Some(0);
+                return (None, 0.0);
+            }
+            Some(GameOutcome::Win(winner)) => {
+                self.pv_table[ply].plies_to_terminal = Some(0);
+                return (None, mate_value(winner == player, ply) * self.discount_at(ply));
+            }
+            // Repeating a position is as good as a draw
+            None if tree.is_draw(state, player, &self.line) => {
+                self.pv_table[ply].plies_to_terminal = Some(0);
+                return (None, 0.0);
+            }
+            // A pending challenge is about to swing trust, so settle it first
+            None if depth == 0 && state.phase == Phase::Challenge => {
+                return (None, self.quiescence(tree, state, alpha, beta, player, root_side));
+            }
+            None if depth == 0 => {
+                return (None, sign * self.evaluate_leaf(state, perspective) * self.discount_at(ply));
+            }
+            None => {}
+        }
+
+        let tt_key = self.transposition_key(state, player, root_side);
+        let window = (alpha, beta);
+        if let Some(key) = tt_key.filter(|_| ply > 0) {
+            if let Some(mut entry) = self.transpositions.get(&key).filter(|entry| entry.depth >= depth) {
+                self.tt_hits += 1;
+                entry.value = mate_from_table(entry.value, ply);
+                // The stored value was searched at least as deep as asked for
+                self.min_remaining_depth = 0;
+                match entry.bound {
+                    Bound::Exact => {}
+                    Bound::Lower => alpha = alpha.max(entry.value),
+                    Bound::Upper => beta = beta.min(entry.value),
+                }
+                if entry.bound == Bound::Exact || alpha >= beta {
+                    if let Some(best) = &entry.best_move {
+                        self.pv_table[ply].moves.push(best.clone());
+                    }
+                    return (entry.best_move, entry.value);
+                }
+            }
+        }
+
+        let mut moves = self.generate_moves(tree, state, player, root_side);
+
+        if moves.is_empty() {
+            self.pv_table[ply + 1] = PvLine::default();
+            let eval = self.no_move_value(tree, state, depth, alpha, beta, player, root_side);
+
+            // A pass is a ply without a move
+            let child = std::mem::take(&mut self.pv_table[ply + 1]);
+            self.pv_table[ply] = PvLine {
+                moves: child.moves,
+                plies_to_terminal: child.plies_to_terminal.map(|plies| plies.saturating_add(1)),
+            };
+            return (None, eval);
+        }
+
+        if !root_side && state.phase == Phase::Challenge {
+            if let (Some(model), Some(claim)) = (self.opponent_model, &state.current_claim) {
+                let challenge_probability = model.challenge_probability(claim);
+                return (None, self.modeled_reply(tree, state, &moves, depth, player, challenge_probability));
+            }
+        }
+
+        if root_side && beta.is_finite() && self.null_move_allowed(state, depth, ply) {
+            // Even handing the opponent the turn does well enough for us
+            let mut passed = null_move(state);
+            let reply_depth = depth - 1 - NULL_MOVE_REDUCTION;
+            let eval = -self.alpha_beta(tree, &mut passed, reply_depth, -beta, -alpha, player.opponent(), false).1;
+
+            if !self.aborted && eval >= beta {
+                self.null_move_cutoffs += 1;
+                self.pv_table[ply] = PvLine::default();
+                return (None, eval);
+            }
+        }
+
+        if self.move_ordering {
+            self.order_moves(tree, state, &mut moves, perspective, sign);
+            self.promote_killers(ply, &mut moves);
+        }
+
+        let (best_move, value) = self.pvs(tree, state, moves, depth, alpha, beta, player, root_side, ply);
+
+        // An abandoned subtree's value is not worth keeping
+        if let Some(key) = tt_key.filter(|_| !self.aborted) {
+            let bound = if value <= window.0 {
+                Bound::Upper
+            } else if value >= window.1 {
+                Bound::Lower
+            } else {
+                Bound::Exact
+            };
+            self.transpositions.insert(
+                key,
+                TranspositionEntry { value: mate_to_table(value, ply), depth, bound, best_move: best_move.clone() },
+            );
+        }
+
+        (best_move, value)
+    }
+
+    /// Transposition table key for a node, or `None` when the table is off
+    /// or node values depend on the ply through discounting
+    fn transposition_key(&self, state: &GameState, player: Player, root_side: bool) -> Option<TranspositionKey> {
+        if self.transpositions.capacity() == 0 || self.discount != 1.0 {
+            return None;
+        }
+
+        Some((state.zobrist_hash(), player, root_side, self.chance_mode))
+    }
+
+    /// Value to the opponent, `player`, of their answer to a claim when they
+    /// challenge with `challenge_probability` and accept otherwise
+    fn modeled_reply(
+        &mut self,
+        tree: &GameTree,
+        state: &mut GameState,
+        moves: &[Move],
+        depth: u8,
+        player: Player,
+        challenge_probability: f64,
+    ) -> f64 {
+        let mut value = 0.0;
+
+        for move_candidate in moves {
+            let weight = match move_candidate.action {
+                Action::Challenge => challenge_probability,
+                Action::Accept => 1.0 - challenge_probability,
+                Action::MakeClaim => 0.0,
+            };
+            if weight == 0.0 {
+                continue;
+            }
+
+            // Replies are mixed afterwards, so each needs an exact score
+            let eval = self.child_value(
+                tree,
+                state,
+                move_candidate,
+                depth.saturating_sub(1),
+                f64::NEG_INFINITY,
+                f64::INFINITY,
+                player,
+                false,
+            );
+            if self.aborted {
+                break;
+            }
+
+            value += weight * eval;
+        }
+
+        value
+    }
+
+    /// Factor applied to leaf values `ply` plies below the root
+    fn discount_at(&self, ply: usize) -> f64 {
+        if self.discount == 1.0 {
+            1.0
+        } else {
+            self.discount.powi(ply as i32)
+        }
+    }
+
+    /// Whether a timed search has run past its deadline or a budgeted one
+    /// has used up its nodes. The clock is only read every
+    /// `TIME_CHECK_INTERVAL` nodes; once passed, stays true.
+    fn out_of_time(&mut self) -> bool {
+        if !self.aborted {
+            if let Some(deadline) = self.deadline {
+                // Never before the first node, so some root move always gets searched
+                let check_due = self.nodes_explored > 0 && self.nodes_explored.is_multiple_of(TIME_CHECK_INTERVAL);
+                if check_due && Instant::now() >= deadline {
+                    self.aborted = true;
+                }
+            }
+            if let Some(limit) = self.node_limit {
+                if self.nodes_explored >= limit {
+                    self.aborted = true;
+                }
+            }
+        }
+
+        self.aborted
+    }
+
+    /// Search `moves` at a node as principal variation search: the first
+    /// move, the best one if ordering is right, gets the full window. Every
+    /// later move is only scouted with a null window just above the best
+    /// value so far to prove it is no better, and is searched again with
+    /// the full window when the scout says it is. With PVS disabled every
+    /// move gets the full window.
+    #[allow(clippy::too_many_arguments)]
+    fn pvs(
+        &mut self,
+        tree: &GameTree,
+        state: &mut GameState,
+        moves: Vec<Move>,
+        depth: u8,
+        mut alpha: f64,
+        beta: f64,
+        player: Player,
+        root_side: bool,
+        ply: usize,
+    ) -> (Option<Move>, f64) {
+        let mut best_eval = f64::NEG_INFINITY;
+        let mut best_move = None;
+
+        for (index, move_candidate) in moves.into_iter().enumerate() {
+            let scout = index > 0 && self.principal_variation_search && alpha.is_finite();
+            let scout_beta = if scout { alpha + PVS_WINDOW } else { beta };
+
+            let mut eval = self.search_move(tree, state, &move_candidate, depth, alpha, scout_beta, player, root_side);
+
+            // The scout failed to prove the move no better: get its real value
+            if scout && !self.aborted && eval > alpha && eval < beta {
+                eval = self.search_move(tree, state, &move_candidate, depth, alpha, beta, player, root_side);
+            }
+
+            // The value of an abandoned subtree is meaningless
+            if self.aborted {
+                break;
+            }
+
+            if eval > best_eval {
+                best_eval = eval;
+                self.update_pv(ply, &move_candidate);
+                best_move = Some(move_candidate);
+            }
+
+            alpha = alpha.max(eval);
+
+            // Cutoff
+            if alpha >= beta {
+                if let Some(cutoff) = &best_move {
+                    self.record_cutoff(ply, depth, cutoff);
+                }
+                break;
+            }
+        }
+
+        (best_move, best_eval)
+    }
+
+    /// Value of a leaf at which a claim awaits its challenge or accept.
+    /// Cutting off there would ignore a trust swing of up to 15 that is
+    /// one reply away, so the replies are searched until the claim is
+    /// resolved and the position is quiet again. Chance outcomes are folded
+    /// per `chance_mode` as usual.
+    fn quiescence(
+        &mut self,
+        tree: &GameTree,
+        state: &mut GameState,
+        alpha: f64,
+        beta: f64,
+        player: Player,
+        root_side: bool,
+    ) -> f64 {
+        // The reply is a ply deeper than the leaf, like an extension
+        self.extensions += 1;
+        let value = self.settle_claim(tree, state, alpha, beta, player, root_side);
+        self.extensions -= 1;
+        value
+    }
+
+    fn settle_claim(
+        &mut self,
+        tree: &GameTree,
+        state: &mut GameState,
+        mut alpha: f64,
+        beta: f64,
+        player: Player,
+        root_side: bool,
+    ) -> f64 {
+        let moves = self.generate_moves(tree, state, player, root_side);
+
+        if !root_side {
+            if let (Some(model), Some(claim)) = (self.opponent_model, &state.current_claim) {
+                let challenge_probability = model.challenge_probability(claim);
+                return self.modeled_reply(tree, state, &moves, 0, player, challenge_probability);
+            }
+        }
+
+        let mut best = f64::NEG_INFINITY;
+
+        for move_candidate in &moves {
+            let eval = self.child_value(tree, state, move_candidate, 0, alpha, beta, player, root_side);
+            if self.aborted {
+                break;
+            }
+
+            best = best.max(eval);
+            alpha = alpha.max(eval);
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        best
+    }
+
+    /// Moves searched at a node: at the opponent's nodes, only those their
+    /// assumed boldness allows
+    fn generate_moves(&self, tree: &GameTree, state: &GameState, player: Player, root_side: bool) -> Vec<Move> {
+        let mut moves = tree.generate_moves(state, player);
+        if let Some(profile) = self.opponent_boldness.filter(|_| !root_side) {
+            profile.restrict(state, &mut moves);
+        }
+        moves
+    }
+
+    /// Value of a non-terminal node without legal moves, per the rules'
+    /// `NoMovePolicy`. Only called with `depth > 0`.
+    #[allow(clippy::too_many_arguments)]
+    fn no_move_value(
+        &mut self,
+        tree: &GameTree,
+        state: &mut GameState,
+        depth: u8,
+        alpha: f64,
+        beta: f64,
+        player: Player,
+        root_side: bool,
+    ) -> f64 {
+        match tree.rules().no_move_policy {
+            NoMovePolicy::Stalemate => 0.0,
+            NoMovePolicy::Pass => {
+                -self.alpha_beta(tree, state, depth - 1, -beta, -alpha, player.opponent(), !root_side).1
+            }
+        }
+    }
+
+    fn evaluate_leaf(&mut self, state: &GameState, perspective: Player) -> f64 {
+        self.cached_evaluation(state, perspective) + self.risk_profile.contempt()
+    }
+
+    fn cached_evaluation(&mut self, state: &GameState, perspective: Player) -> f64 {
+        if self.eval_cache.capacity() == 0 {
+            return self.evaluator.evaluate(state, perspective);
+        }
+
+        let key = (state.state_key(), perspective);
+        if let Some(eval) = self.eval_cache.get(&key) {
+            return eval;
+        }
+
+        let eval = self.evaluator.evaluate(state, perspective);
+        self.eval_cache.insert(key, eval);
+        eval
+    }
+
+    /// Sort `moves` so the likely best are searched first: by history score,
+    /// then by a quick static score for the side to move, both descending.
+    /// The sort is stable, so equal scores keep their generation order.
+    fn order_moves(&self, tree: &GameTree, state: &GameState, moves: &mut [Move], perspective: Player, sign: f64) {
+        let mut scored: Vec<(f64, Move)> = moves
+            .iter()
+            .map(|m| (self.static_score(tree, state, m, perspective, sign), m.clone()))
+            .collect();
+        scored.sort_by(|(a, move_a), (b, move_b)| {
+            self.history_score(move_b).cmp(&self.history_score(move_a)).then(b.total_cmp(a))
+        });
+
+        for (slot, (_, m)) in moves.iter_mut().zip(scored) {
+            *slot = m;
+        }
+    }
+
+    fn history_score(&self, m: &Move) -> u64 {
+        self.history.get(&ordering_key(m)).copied().unwrap_or(0)
+    }
+
+    /// Whether a null move may be tried at this node: the pruning is on,
+    /// enough depth is left, the node is not the root, and trust is far
+    /// enough from the thresholds that passing cannot decide the game
+    fn null_move_allowed(&self, state: &GameState, depth: u8, ply: usize) -> bool {
+        let near_threshold = |trust: i32| {
+            trust >= self.rules.win_trust - NULL_MOVE_TRUST_MARGIN
+                || trust <= self.rules.lose_trust + NULL_MOVE_TRUST_MARGIN
+        };
+
+        self.enable_null_move
+            && depth >= NULL_MOVE_MIN_DEPTH
+            && ply > 0
+            && state.phase == Phase::Claim
+            && !near_threshold(state.player1_trust)
+            && !near_threshold(state.player2_trust)
+    }
+
+    /// Move the killer moves of `ply` to the front of `moves`, most recent
+    /// first. A killer matches any move with the same `ordering_key`.
+    fn promote_killers(&self, ply: usize, moves: &mut Vec<Move>) {
+        let Some(slots) = self.killers.get(ply) else { return };
+
+        for killer in slots.iter().rev().flatten() {
+            if let Some(index) = moves.iter().position(|m| ordering_key(m) == ordering_key(killer)) {
+                let promoted = moves.remove(index);
+                moves.insert(0, promoted);
+            }
+        }
+    }
+
+    /// Remember that `cutoff` caused a cutoff at `ply` with `depth` left to
+    /// search: as a killer move, and in the history table with a weight of
+    /// `depth²`, so cutoffs high in the tree count for more. The game has no
+    /// captures, so every move is a candidate.
+    fn record_cutoff(&mut self, ply: usize, depth: u8, cutoff: &Move) {
+        if !self.move_ordering {
+            return;
+        }
+
+        *self.history.entry(ordering_key(cutoff)).or_insert(0) += (depth as u64).pow(2);
+
+        if self.killers.len() <= ply {
+            self.killers.resize(ply + 1, [None, None]);
+        }
+
+        let slots = &mut self.killers[ply];
+        if slots[0].as_ref().map(ordering_key) != Some(ordering_key(cutoff)) {
+            slots[1] = slots[0].take();
+            slots[0] = Some(cutoff.clone());
+        }
+    }
+
+    /// Expected evaluation of the state `move_made` leads to, scored from
+    /// `perspective` and multiplied by `sign` for the side to move. The
+    /// evaluator does not look at a pending claim, so a claim is scored by
+    /// the opponent's best immediate reply to it instead.
+    fn static_score(
+        &self,
+        tree: &GameTree,
+        state: &GameState,
+        move_made: &Move,
+        perspective: Player,
+        sign: f64,
+    ) -> f64 {
+        let expected = |state: &GameState, m: &Move| -> f64 {
+            let value: f64 = tree
+                .resolution_outcomes(state, m)
+                .iter()
+                .map(|(outcome, probability)| self.evaluator.evaluate(outcome, perspective) * probability)
+                .sum();
+            sign * value
+        };
+
+        if move_made.action != Action::MakeClaim {
+            return expected(state, move_made);
+        }
+
+        let child = tree.resolution_outcomes(state, move_made).swap_remove(0).0;
+        let replies = tree.generate_moves(&child, move_made.player.opponent());
+        let values = replies.iter().map(|reply| expected(&child, reply));
+
+        // The reply comes from the other side of this node
+        let best_reply = values.fold(f64::INFINITY, f64::min);
+
+        if best_reply.is_finite() {
+            best_reply
+        } else {
+            sign * self.evaluator.evaluate(&child, perspective)
+        }
+    }
+
+    /// `child_value` of `move_made` played at a node with `depth` left,
+    /// searched one ply shallower unless the depth policy extends it
+    #[allow(clippy::too_many_arguments)]
+    fn search_move(
+        &mut self,
+        tree: &GameTree,
+        state: &mut GameState,
+        move_made: &Move,
+        depth: u8,
+        alpha: f64,
+        beta: f64,
+        player: Player,
+        root_side: bool,
+    ) -> f64 {
+        if !self.depth_policy.extends(move_made, self.extensions) {
+            return self.child_value(tree, state, move_made, depth.saturating_sub(1), alpha, beta, player, root_side);
+        }
+
+        self.extensions += 1;
+        let value = self.child_value(tree, state, move_made, depth, alpha, beta, player, root_side);
+        self.extensions -= 1;
+        value
+    }
+
+    /// Value to `player` of playing `move_made`, folding chance outcomes
+    /// per `chance_mode`. The reply is searched with the window negated and
+    /// swapped, and its value negated back.
+    #[allow(clippy::too_many_arguments)]
+    fn child_value(
+        &mut self,
+        tree: &GameTree,
+        state: &mut GameState,
+        move_made: &Move,
+        depth: u8,
+        alpha: f64,
+        beta: f64,
+        player: Player,
+        root_side: bool,
+    ) -> f64 {
+        let opponent = player.opponent();
+
+        if self.chance_mode == ChanceMode::Sampled {
+            let undo = tree.make_move(state, move_made);
+            let value = -self.alpha_beta(tree, state, depth, -beta, -alpha, opponent, !root_side).1;
+            tree.undo_move(state, undo);
+            return value;
+        }
+
+        let probabilities = tree.outcome_probabilities(state, move_made);
+
+        if probabilities.len() == 1 {
+            let undo = tree.make_outcome(state, move_made, 0);
+            let value = -self.alpha_beta(tree, state, depth, -beta, -alpha, opponent, !root_side).1;
+            tree.undo_move(state, undo);
+            return value;
+        }
+
+        // Outcome values are combined afterwards, so each needs an exact score
+        let values = probabilities.iter().enumerate().map(|(outcome, probability)| {
+            let undo = tree.make_outcome(state, move_made, outcome);
+            let (_, eval) = self.alpha_beta(
+                tree,
+                state,
+                depth,
+                f64::NEG_INFINITY,
+                f64::INFINITY,
+                opponent,
+                !root_side,
+            );
+            tree.undo_move(state, undo);
+            (-eval, *probability)
+        }).collect::<Vec<_>>();
+
+        match self.chance_mode {
+            // Worst for the searching player, whoever is to move
+            ChanceMode::WorstCase if root_side => values.iter().map(|(eval, _)| *eval).fold(f64::INFINITY, f64::min),
+            ChanceMode::WorstCase => values.iter().map(|(eval, _)| *eval).fold(f64::NEG_INFINITY, f64::max),
+            _ => values.iter().map(|(eval, probability)| eval * probability).sum(),
+        }
+    }
+
+    /// Search the root by giving each move to a worker of its own, like
+    /// `alpha_beta` on the same node but without bounds shared between
+    /// root moves
+    fn parallel_alpha_beta(
+        &mut self,
+        tree: &GameTree,
+        state: &GameState,
+        depth: u8,
+        player: Player,
+        root_side: bool,
+    ) -> (Option<Move>, f64) {
+        let root_line = [self.line.as_slice(), &[(state.zobrist_hash(), player)]].concat();
+
+        // A finished game or a repeated position has nothing to split
+        if tree.outcome(state).is_some() || tree.is_draw(state, player, &root_line) {
+            return self.alpha_beta(tree, &mut state.clone(), depth, f64::NEG_INFINITY, f64::INFINITY, player, root_side);
+        }
+
+        self.nodes_explored += 1;
+
+        let moves = tree.generate_moves(state, player);
+
+        if moves.is_empty() {
+            let eval = self.no_move_value(tree, &mut state.clone(), depth, f64::NEG_INFINITY, f64::INFINITY, player, root_side);
+            return (None, eval);
+        }
+
+        let this = &*self;
+
+        // Evaluate root moves in parallel, each in a worker of its own
+        let results: Vec<WorkerResult> = moves
+            .par_iter()
+            .map(|move_candidate| {
+                let mut local_search = this.worker(depth - 1);
+                local_search.begin_iteration(depth);
+                local_search.line = root_line.clone();
+                let eval = local_search.search_move(
+                    tree,
+                    &mut state.clone(),
+                    move_candidate,
+                    depth,
+                    f64::NEG_INFINITY,
+                    f64::INFINITY,
+                    player,
+                    root_side,
+                );
+                WorkerResult {
+                    move_made: move_candidate.clone(),
+                    eval,
+                    line: std::mem::take(&mut local_search.pv_table[1]),
+                    nodes_explored: local_search.nodes_explored,
+                    null_move_cutoffs: local_search.null_move_cutoffs,
+                    min_remaining_depth: local_search.min_remaining_depth,
+                }
+            })
+            .collect();
+
+        for result in &results {
+            self.nodes_explored += result.nodes_explored;
+            self.null_move_cutoffs += result.null_move_cutoffs;
+            self.min_remaining_depth = self.min_remaining_depth.min(result.min_remaining_depth);
+        }
+
+        let best = results.into_iter().max_by(|a, b| a.eval.total_cmp(&b.eval)).unwrap();
+        let (best_move, best_eval, line) = (best.move_made, best.eval, best.line);
+
+        self.pv_table[1] = line;
+        self.update_pv(0, &best_move);
+
+        (Some(best_move), best_eval)
+    }
+
+    /// Uncached serial search with this search's configuration, for the
+    /// parallel root split
+    fn worker(&self, max_depth: u8) -> AlphaBetaSearch {
+        let mut worker = AlphaBetaSearch::new(max_depth, false);
+        worker.evaluator = Arc::clone(&self.evaluator);
+        worker.chance_mode = self.chance_mode;
+        worker.rules = self.rules;
+        worker.seed = self.seed;
+        worker.opponent_model = self.opponent_model;
+        worker.risk_profile = self.risk_profile;
+        worker.claim_levels = self.claim_levels.clone();
+        worker.accept_limit = self.accept_limit;
+        worker.opponent_boldness = self.opponent_boldness;
+        worker.discount = self.discount;
+        worker.move_ordering = self.move_ordering;
+        worker.principal_variation_search = self.principal_variation_search;
+        worker.enable_null_move = self.enable_null_move;
+        worker.depth_policy = self.depth_policy;
+        worker
+    }
+
+    fn default_move(&self, _state: &GameState, player: Player) -> Move {
+        Move {
+            action: crate::Action::Accept,
+            player,
+            claim: None,
+            confidence: 0.5,
+            trust_after: None,
+        }
+    }
+
+    /// Has no effect on wasm32, where searches always run serially
+    pub fn set_parallel(&mut self, enable: bool) {
+        self.enable_parallel = enable && PARALLEL_AVAILABLE;
+    }
+
+    /// Prune a subtree when passing the turn to the opponent, searched
+    /// `NULL_MOVE_REDUCTION` plies shallower, already fails high. Tried at
+    /// the searching player's nodes in the claim phase with at least
+    /// `NULL_MOVE_MIN_DEPTH` plies left, but never close to a trust
+    /// threshold, where the move actually played can decide the game.
+    /// Unsound in principle, since having to move is not always an
+    /// advantage. Disabled by default.
+    pub fn set_null_move(&mut self, enable: bool) {
+        self.enable_null_move = enable;
+    }
+
+    /// Before the first parallel `search`, run a quick serial depth-2 probe
+    /// and keep this search serial for good if the probe's tree is too small
+    /// for the parallel root split to pay off. Has no effect on serial
+    /// searches. Disabled by default.
+    pub fn set_parallel_probe(&mut self, enable: bool) {
+        self.parallel_probe = enable;
+        self.probe_result = None;
+    }
+
+    /// Let each root move's own confidence shift its selection score by
+    /// `weight * (confidence - 0.5) * 20`, for personalities that favor moves
+    /// they believe in. 0 (the default) selects purely by evaluation.
+    /// A nonzero weight searches every root move with a full window.
+    pub fn set_confidence_weight(&mut self, weight: f64) {
+        self.confidence_weight = weight;
+    }
+
+    /// Discourage predictable play: at the root, subtract `penalty` from any
+    /// claim whose type this player used in their last `window` moves.
+    /// Affects move selection only, not evaluation. 0 disables it.
+    pub fn set_diversity_penalty(&mut self, penalty: f64, window: u8) {
+        self.diversity_penalty = penalty;
+        self.diversity_window = window;
+    }
+
+    /// Sort moves by a quick static score before searching them, trying
+    /// killer moves (recent cutoffs at the same ply) first, so that
+    /// alpha-beta cuts off earlier. Enabled by default; disable it to
+    /// benchmark the pruning it buys. Only the order of equally valued
+    /// moves, and so the choice between them, can change.
+    pub fn set_move_ordering(&mut self, enable: bool) {
+        self.move_ordering = enable;
+    }
+
+    /// Number of kinds of move in the history table. The table builds up
+    /// over searches, iterative deepening iterations included, and is never
+    /// cleared on its own.
+    pub fn history_table_size(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Forget the history table, e.g. before searching an unrelated game
+    pub fn reset_history(&mut self) {
+        self.history.clear();
+    }
+
+    /// Search moves after the first with a null window before committing to
+    /// a full search of them (principal variation search). Pays off when
+    /// move ordering usually puts the best move first. Enabled by default;
+    /// the best value found is the same either way.
+    pub fn set_principal_variation_search(&mut self, enable: bool) {
+        self.principal_variation_search = enable;
+    }
+
+    /// Scale leaf values by `gamma^ply`, so that an outcome reached in fewer
+    /// plies is worth more. 1.0 (the default) disables discounting.
+    pub fn set_discount(&mut self, gamma: f64) {
+        self.discount = gamma;
+    }
+}
+
+/// Factor turning a value for the searching player into one for the side
+/// to move
+fn side_sign(root_side: bool) -> f64 {
+    if root_side { 1.0 } else { -1.0 }
+}
+
+/// A node's value as stored in the transposition table: a win or loss
+/// counted in plies from the node instead of from the root, since the
+/// same position can come up at different plies
+fn mate_to_table(value: f64, ply: usize) -> f64 {
+    if value >= MATE_THRESHOLD {
+        value + ply as f64
+    } else if value <= -MATE_THRESHOLD {
+        value - ply as f64
+    } else {
+        value
+    }
+}
+
+/// Inverse of `mate_to_table` for a node `ply` plies below the root
+fn mate_from_table(value: f64, ply: usize) -> f64 {
+    if value >= MATE_THRESHOLD {
+        value - ply as f64
+    } else if value <= -MATE_THRESHOLD {
+        value + ply as f64
+    } else {
+        value
+    }
+}
+
+/// `state` with the claim turn handed to the opponent without a claim.
+/// The game has no pass, so this is only a hypothetical for null-move
+/// pruning.
+fn null_move(state: &GameState) -> GameState {
+    let mut passed = state.clone();
+    passed.phase = Phase::Claim;
+    passed.current_claim = None;
+    passed
+}
+
+/// What identifies a kind of move for killer moves and the history table:
+/// the action and the type of claim it makes. Boldness and confidence are
+/// ignored, so a killer also promotes the same kind of claim at a
+/// different boldness.
+fn ordering_key(m: &Move) -> OrderingKey {
+    (m.action, m.claim.as_ref().map(|claim| claim.claim_type))
+}
+
+/// Simpler variants of `state` to try when minimizing, most aggressive first
+fn shrink_candidates(state: &GameState) -> Vec<GameState> {
+    let mut candidates = Vec::new();
+    let history = state.move_history.len();
+
+    // Shorter history: none, the later half, all but the oldest move
+    for keep in [0, history / 2, history.saturating_sub(1)] {
+        if keep < history {
+            let mut candidate = state.clone();
+            candidate.move_history.drain(..history - keep);
+            candidates.push(candidate);
+        }
+    }
+
+    // Trust toward 50: all the way, then halfway
+    let toward = |trust: i32| [50, trust + (50 - trust) / 2];
+    for target in toward(state.player1_trust) {
+        if target != state.player1_trust {
+            candidates.push(GameState { player1_trust: target, ..state.clone() });
+        }
+    }
+    for target in toward(state.player2_trust) {
+        if target != state.player2_trust {
+            candidates.push(GameState { player2_trust: target, ..state.clone() });
+        }
+    }
+
+    candidates
+}
+
+/// Seed used by `search_node_count` so golden counts are reproducible
+pub const GOLDEN_SEED: u64 = 0x5eed;
+
+/// Number of nodes a serial search of `state` explores at `depth`, using the
+/// default rules and weights in integer mode and challenges resolved from `GOLDEN_SEED`.
+///
+/// Intended for golden tests: pin the count for a canonical position so that
+/// unintended changes to move generation, ordering or pruning show up as a
+/// changed number. Update the pinned value only for deliberate changes.
+pub fn search_node_count(state: &GameState, depth: u8) -> u64 {
+    let mut search = AlphaBetaSearch::new(depth, false)
+        .with_seed(GOLDEN_SEED)
+        .with_evaluator(Evaluator::new().integer_mode(true));
+    search.search(state, Player::Player1).nodes_explored
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Action, MinimaxSearch, Phase};
+    use proptest::prelude::*;
+
+    fn create_test_state() -> GameState {
+        GameState {
+            round: 1,
+            phase: Phase::Claim,
             player1_trust: 50,
             player2_trust: 50,
             current_claim: None,
@@ -218,20 +2015,1202 @@ mod tests {
     }
 
     #[test]
-    fn test_alpha_beta_search() {
-        let mut search = AlphaBetaSearch::new(4, false);
+    fn test_alpha_beta_search() {
+        let mut search = AlphaBetaSearch::new(4, false);
+        let state = create_test_state();
+        let result = search.search(&state, Player::Player1);
+        
+        assert!(result.nodes_explored > 0);
+    }
+
+    #[test]
+    fn test_parallel_search() {
+        let mut search = AlphaBetaSearch::new(4, true);
+        let state = create_test_state();
+        let result = search.search(&state, Player::Player1);
+        
+        assert!(result.nodes_explored > 0);
+    }
+
+    #[test]
+    fn test_parallel_agrees_with_serial() {
+        for (seed, (round, p1_trust, p2_trust)) in [(1, 50, 50), (7, 65, 40), (13, 30, 55)].into_iter().enumerate() {
+            let mut state = create_test_state();
+            state.round = round;
+            state.player1_trust = p1_trust;
+            state.player2_trust = p2_trust;
+
+            let serial = AlphaBetaSearch::new(4, false).with_seed(seed as u64).search(&state, Player::Player1);
+            let parallel = AlphaBetaSearch::new(4, true).with_seed(seed as u64).search(&state, Player::Player1);
+            assert!((parallel.evaluation - serial.evaluation).abs() < 1e-9);
+
+            // So does a root searched on the opponent's behalf
+            let tree = GameTree::with_seed(state.clone(), seed as u64);
+            let mut search = AlphaBetaSearch::new(4, true).with_seed(seed as u64);
+            search.begin_search(&tree, &state, 4);
+            let (_, min_eval) = search.parallel_alpha_beta(&tree, &state, 4, Player::Player1, false);
+
+            let mut serial = AlphaBetaSearch::new(4, false).with_seed(seed as u64);
+            serial.begin_search(&tree, &state, 4);
+            let (_, serial_min) = serial.alpha_beta(&tree, &mut state.clone(), 4, f64::NEG_INFINITY, f64::INFINITY, Player::Player1, false);
+            assert!((min_eval - serial_min).abs() < 1e-9);
+        }
+    }
+
+    /// Any valid position: trust mostly around the thresholds, where games
+    /// are decided, but anywhere within `MAX_TRUST`, and a standing claim
+    /// of any type, boldness and honesty in the challenge phase
+    fn any_state() -> impl Strategy<Value = GameState> {
+        let trust = || prop_oneof![3 => -60..=110i32, 1 => -crate::MAX_TRUST..=crate::MAX_TRUST];
+        let claim_types = [ClaimType::Information, ClaimType::Prediction, ClaimType::Accusation, ClaimType::Alliance];
+        let claim = proptest::option::of((0..4usize, 0..=10u8, any::<bool>())).prop_map(move |claim| {
+            claim.map(|(claim_type, tenths, is_bluff)| crate::Claim {
+                description: String::new(),
+                claim_type: claim_types[claim_type],
+                boldness: tenths as f64 / 10.0,
+                is_bluff,
+            })
+        });
+
+        let round = prop_oneof![3 => 1..=20u8, 1 => any::<u8>()];
+
+        (round, trust(), trust(), claim).prop_map(|(round, player1_trust, player2_trust, current_claim)| GameState {
+            round,
+            phase: if current_claim.is_some() { Phase::Challenge } else { Phase::Claim },
+            player1_trust,
+            player2_trust,
+            current_claim,
+            move_history: Vec::new(),
+        })
+    }
+
+    proptest! {
+        // Every case runs three searches up to depth 6
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        /// Both engines score every line alike: finished games by mate
+        /// distance, repeated positions as draws and claims pending at a
+        /// leaf once they are settled. Moves of equal value may be told
+        /// apart differently, so minimax's move need only be one of
+        /// alpha-beta's best.
+        #[test]
+        fn test_alpha_beta_matches_minimax_on_random_states(
+            state in any_state(),
+            depth in 1..=6u8,
+            // Inconclusive challenges keep the round, so lines can repeat
+            inconclusive_probability in prop_oneof![Just(0.0), 0.0..=1.0],
+            player1 in any::<bool>(),
+            parallel in any::<bool>(),
+            seed in any::<u64>(),
+        ) {
+            let player = if player1 { Player::Player1 } else { Player::Player2 };
+            let rules = GameRules {
+                inconclusive_probability,
+                ..GameRules::default()
+            };
+            let minimax = MinimaxSearch::new(depth).with_rules(rules).with_seed(seed).search(&state, player);
+            let alpha_beta = AlphaBetaSearch::new(depth, parallel)
+                .with_rules(rules)
+                .with_seed(seed)
+                .search(&state, player);
+            prop_assert!(
+                (alpha_beta.evaluation - minimax.evaluation).abs() < 1e-9,
+                "alpha-beta {} vs minimax {}",
+                alpha_beta.evaluation,
+                minimax.evaluation
+            );
+
+            let scored = AlphaBetaSearch::new(depth, false)
+                .with_rules(rules)
+                .with_seed(seed)
+                .search_multipv(&state, player, usize::MAX);
+            if let Some(chosen) = scored.iter().find(|result| result.best_move == minimax.best_move) {
+                prop_assert!(
+                    (chosen.evaluation - alpha_beta.evaluation).abs() < 1e-9,
+                    "minimax's {:?} is worth {} to alpha-beta, not {}",
+                    minimax.best_move.action,
+                    chosen.evaluation,
+                    alpha_beta.evaluation
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_dedicated_thread_pool() {
+        let state = create_test_state();
+        let serial = AlphaBetaSearch::new(4, false).with_seed(GOLDEN_SEED).search(&state, Player::Player1);
+
+        let mut pooled = AlphaBetaSearch::new(4, true).with_seed(GOLDEN_SEED).with_thread_pool(2);
+        let result = pooled.search(&state, Player::Player1);
+        assert_eq!(result.best_move.player, Player::Player1);
+        assert!((result.evaluation - serial.evaluation).abs() < 1e-9);
+        assert_eq!(pooled.estimated_memory_bytes(), 2 * AlphaBetaSearch::new(4, false).estimated_memory_bytes());
+
+        // 0 falls back to the global pool
+        assert!(AlphaBetaSearch::new(4, true).with_thread_pool(0).thread_pool.is_none());
+    }
+
+    #[test]
+    fn test_opening_book_move_skips_search() {
+        let book_move = Move {
+            action: Action::MakeClaim,
+            player: Player::Player1,
+            claim: Some(crate::Claim {
+                description: "opening".to_string(),
+                claim_type: ClaimType::Alliance,
+                boldness: 0.2,
+                is_bluff: false,
+            }),
+            confidence: Move::claim_confidence(0.2),
+            trust_after: None,
+        };
+        let mut book = OpeningBook::default();
+        book.insert(&create_test_state(), book_move.clone());
+        let mut search = AlphaBetaSearch::new(4, false).with_seed(GOLDEN_SEED).with_opening_book(book);
+
+        let result = search.search(&create_test_state(), Player::Player1);
+        assert_eq!(result.best_move, book_move);
+        assert_eq!(result.nodes_explored, 0);
+        let budgeted = search.search_node_budgeted(&create_test_state(), Player::Player1, 1000);
+        assert_eq!(budgeted.best_move, book_move);
+
+        // Out of book: an ordinary search
+        let mut later = create_test_state();
+        later.round = 5;
+        assert!(search.search(&later, Player::Player1).nodes_explored > 0);
+    }
+
+    #[test]
+    fn test_parallel_counts_worker_nodes() {
+        let state = create_test_state();
+        let serial = AlphaBetaSearch::new(5, false).with_seed(GOLDEN_SEED).search(&state, Player::Player1);
+        let parallel = AlphaBetaSearch::new(5, true).with_seed(GOLDEN_SEED).search(&state, Player::Player1);
+
+        // Workers search their root moves with full windows and share no
+        // bounds, so they explore more, but the same order of magnitude
+        assert!(parallel.nodes_explored >= serial.nodes_explored);
+        assert!(parallel.nodes_explored < serial.nodes_explored * 4);
+    }
+
+    #[test]
+    fn test_parallel_probe_keeps_tiny_tree_serial() {
+        // Nothing to do in resolution, so both players can only pass
+        let mut state = create_test_state();
+        state.phase = Phase::Resolution;
+
+        let serial = AlphaBetaSearch::new(8, false).with_seed(3).search(&state, Player::Player1);
+
+        let mut probed = AlphaBetaSearch::new(8, true).with_seed(3);
+        probed.set_parallel_probe(true);
+        let result = probed.search(&state, Player::Player1);
+
+        assert_eq!(probed.probe_result, Some(false));
+        assert_eq!(result.nodes_explored, serial.nodes_explored);
+        assert!(result.nodes_explored > 1);
+
+        // A full-size tree still goes parallel
+        let mut probed = AlphaBetaSearch::new(5, true).with_seed(3);
+        probed.set_parallel_probe(true);
+        probed.search(&create_test_state(), Player::Player1);
+        assert_eq!(probed.probe_result, Some(true));
+    }
+
+    #[test]
+    fn test_search_streaming_matches_scored() {
+        let state = create_test_state();
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        let mut search = AlphaBetaSearch::new(4, false).with_seed(11);
+        let result = search.search_streaming(&state, Player::Player1, 3, tx);
+        let streamed: Vec<(Move, f64)> = rx.iter().collect();
+
+        let scored = AlphaBetaSearch::new(4, false)
+            .with_seed(11)
+            .search_scored(&state, Player::Player1, 3);
+        assert_eq!(streamed, scored);
+
+        let best = streamed.iter().map(|(_, eval)| *eval).fold(f64::NEG_INFINITY, f64::max);
+        assert_eq!(result.evaluation, best);
+    }
+
+    #[test]
+    fn test_node_budget_is_reproducible() {
+        let state = create_test_state();
+        let run = || AlphaBetaSearch::new(8, false).with_seed(5).search_node_budgeted(&state, Player::Player1, 150);
+
+        let first = run();
+        let second = run();
+
+        assert_eq!(first.nodes_explored, 150);
+        assert_eq!(second.nodes_explored, first.nodes_explored);
+        assert_eq!(second.best_move, first.best_move);
+        assert_eq!(second.evaluation, first.evaluation);
+        assert_eq!(second.depth_reached, first.depth_reached);
+        assert!(first.depth_reached > 0);
+    }
+
+    #[test]
+    fn test_minimize_state_keeps_reported_move() {
+        // Player 2 faces a bold bluff after a long game
+        let claim = crate::Claim {
+            description: "claim".to_string(),
+            claim_type: crate::ClaimType::Accusation,
+            boldness: 0.8,
+            is_bluff: true,
+        };
+        let mut state = create_test_state();
+        state.phase = Phase::Challenge;
+        state.player1_trust = 85;
+        state.player2_trust = 20;
+        for i in 0..10 {
+            state.move_history.push(Move {
+                action: if i % 2 == 0 { Action::MakeClaim } else { Action::Accept },
+                player: if i % 2 == 0 { Player::Player1 } else { Player::Player2 },
+                claim: Some(claim.clone()),
+                confidence: 0.6,
+                trust_after: None,
+            });
+        }
+        state.current_claim = Some(claim);
+
+        let challenges = |result: &SearchResult| result.best_move.action == Action::Challenge;
+        let mut search = AlphaBetaSearch::new(4, false).with_seed(9);
+        assert!(challenges(&search.search_to_depth(&state, Player::Player2, 2)));
+
+        let minimal = search.minimize_state(&state, challenges, Player::Player2, 2);
+
+        assert!(minimal.move_history.len() < state.move_history.len());
+        assert!((minimal.player1_trust - 50).abs() < (state.player1_trust - 50).abs());
+        assert!((minimal.player2_trust - 50).abs() < (state.player2_trust - 50).abs());
+        assert!(challenges(&search.search_to_depth(&minimal, Player::Player2, 2)));
+    }
+
+    #[test]
+    fn test_credulous_opponent_invites_bluffing() {
+        // Bold claims usually hold up here, so bluffing pays unless the
+        // opponent sees through it
+        let rules = GameRules {
+            challenge_base: 0.2,
+            challenge_boldness_factor: -0.7,
+            ..GameRules::default()
+        };
+        let bluff_rate = |skill: f64| {
+            AlphaBetaSearch::new(3, false)
+                .with_rules(rules)
+                .with_opponent_model(OpponentModel::new(skill))
+                .optimal_bluff_rate(&create_test_state(), Player::Player1, 3)
+        };
+
+        let credulous = bluff_rate(0.1);
+        let skeptical = bluff_rate(0.9);
+
+        assert!(credulous <= 1.0);
+        assert!(skeptical >= 0.0);
+        assert!(credulous > skeptical + 0.25, "credulous {credulous} vs skeptical {skeptical}");
+    }
+
+    #[test]
+    fn test_timid_opponent_invites_bluffing() {
+        // Best value of any claim, and of any bluff, with the outcomes of
+        // challenges weighted by their probability
+        let best_values = |opponent: Option<BoldnessProfile>| {
+            let mut search = AlphaBetaSearch::new(2, false);
+            search.chance_mode = ChanceMode::Expected;
+            if let Some(profile) = opponent {
+                search = search.with_opponent_boldness(profile);
+            }
+
+            let scored = search.search_scored(&create_test_state(), Player::Player1, 2);
+            let best = |bluffs_only: bool| {
+                scored
+                    .iter()
+                    .filter(|(m, _)| !bluffs_only || m.claim.as_ref().unwrap().is_bluff)
+                    .map(|(_, value)| *value)
+                    .fold(f64::NEG_INFINITY, f64::max)
+            };
+            (best(false), best(true))
+        };
+
+        // A bluff only invites a costly challenge from an opponent who dares
+        let (best, best_bluff) = best_values(None);
+        assert!(best_bluff < best);
+
+        let (timid_best, timid_bluff) = best_values(Some(BoldnessProfile::Timid));
+        assert_eq!(timid_bluff, timid_best);
+        assert!(timid_bluff > best_bluff);
+    }
+
+    #[test]
+    fn test_transposition_table_shares_positions() {
+        let claim = |claim_type: crate::ClaimType| Move {
+            action: Action::MakeClaim,
+            player: Player::Player2,
+            claim: Some(crate::Claim {
+                description: String::new(),
+                claim_type,
+                boldness: 0.2,
+                is_bluff: false,
+            }),
+            confidence: 0.94,
+            trust_after: None,
+        };
+        // The same position reached by different histories
+        let mut first = create_test_state();
+        first.move_history = vec![claim(crate::ClaimType::Information)];
+        let mut second = create_test_state();
+        second.move_history = vec![claim(crate::ClaimType::Alliance)];
+
+        let mut search = AlphaBetaSearch::new(4, false)
+            .with_seed(2)
+            .with_transposition_table(10_000);
+        let fresh = search.search(&first, Player::Player1);
+
+        // Accepting any claim ends the round in the same position, so
+        // positions repeat within a single search
+        assert!(fresh.tt_hits > 0);
+
+        let reused = search.search(&second, Player::Player1);
+        assert!(reused.tt_hits > 0);
+        assert!(reused.nodes_explored < fresh.nodes_explored);
+
+        // Without a table nothing is stored or probed
+        let plain = AlphaBetaSearch::new(4, false).with_seed(2).search(&first, Player::Player1);
+        assert_eq!(plain.tt_hits, 0);
+        assert!(plain.nodes_explored > fresh.nodes_explored);
+    }
+
+    #[test]
+    fn test_principal_variation_starts_with_best_move() {
+        let state = create_test_state();
+
+        for parallel in [false, true] {
+            let mut search = AlphaBetaSearch::new(5, parallel).with_seed(8);
+            let result = search.search(&state, Player::Player1);
+
+            assert_eq!(result.principal_variation.first(), Some(&result.best_move));
+            assert!(result.principal_variation.len() > 1);
+            assert!(result.principal_variation.len() <= result.depth_reached as usize);
+
+            // Players alternate along the line
+            for pair in result.principal_variation.windows(2) {
+                assert_ne!(pair[0].player, pair[1].player);
+            }
+        }
+
+        let mut timed = AlphaBetaSearch::new(4, false).with_seed(8);
+        let result = timed.search_timed(&state, Player::Player1, Duration::from_secs(5));
+        assert_eq!(result.principal_variation.first(), Some(&result.best_move));
+        assert!(result.principal_variation.len() <= result.depth_reached as usize);
+    }
+
+    #[test]
+    fn test_move_ordering_prunes_more() {
+        // Bolder claims hold up more often under these rules, so generation
+        // order (mildest first) is the worst order for the claimer
+        let rules = GameRules {
+            challenge_base: 0.2,
+            challenge_boldness_factor: -0.7,
+            ..GameRules::default()
+        };
         let state = create_test_state();
-        let result = search.search(&state, Player::Player1);
-        
-        assert!(result.nodes_explored > 0);
+
+        let mut ordered = AlphaBetaSearch::new(5, false).with_rules(rules).with_seed(GOLDEN_SEED);
+        let mut unordered = AlphaBetaSearch::new(5, false).with_rules(rules).with_seed(GOLDEN_SEED);
+        unordered.set_move_ordering(false);
+
+        let ordered_result = ordered.search(&state, Player::Player1);
+        let unordered_result = unordered.search(&state, Player::Player1);
+
+        assert!(ordered_result.nodes_explored < unordered_result.nodes_explored);
+        assert_eq!(ordered_result.evaluation, unordered_result.evaluation);
     }
 
     #[test]
-    fn test_parallel_search() {
-        let mut search = AlphaBetaSearch::new(4, true);
+    fn test_killer_table_populated_after_deep_search() {
+        let mut search = AlphaBetaSearch::new(5, false).with_seed(GOLDEN_SEED);
+        search.search(&create_test_state(), Player::Player1);
+
+        assert!(search.killers.iter().any(|slots| slots[0].is_some()));
+        for slots in &search.killers {
+            // A ply never holds the same kind of move twice
+            if let [Some(first), Some(second)] = slots {
+                assert_ne!(ordering_key(first), ordering_key(second));
+            }
+        }
+
+        // Without ordering the killers are neither used nor recorded
+        let mut unordered = AlphaBetaSearch::new(5, false).with_seed(GOLDEN_SEED);
+        unordered.set_move_ordering(false);
+        unordered.search(&create_test_state(), Player::Player1);
+        assert!(unordered.killers.is_empty());
+    }
+
+    #[test]
+    fn test_history_table_persists_until_reset() {
         let state = create_test_state();
-        let result = search.search(&state, Player::Player1);
-        
+        let mut search = AlphaBetaSearch::new(4, false).with_seed(GOLDEN_SEED);
+        assert_eq!(search.history_table_size(), 0);
+
+        search.search_node_budgeted(&state, Player::Player1, 400);
+        let size = search.history_table_size();
+        let scores = search.history.clone();
+        assert!(size > 0);
+
+        // A second search builds on the first one's scores
+        search.search(&state, Player::Player1);
+        assert!(search.history_table_size() >= size);
+        assert!(scores.iter().all(|(key, score)| search.history[key] >= *score));
+
+        search.reset_history();
+        assert_eq!(search.history_table_size(), 0);
+    }
+
+    #[test]
+    fn test_pvs_matches_plain_alpha_beta() {
+        let mut states = Vec::new();
+        for (round, p1_trust, p2_trust) in [(1, 50, 50), (6, 70, 35), (12, 20, 65), (18, 55, 50)] {
+            let mut state = create_test_state();
+            state.round = round;
+            state.player1_trust = p1_trust;
+            state.player2_trust = p2_trust;
+            states.push(state);
+        }
+
+        for (seed, state) in states.iter().enumerate() {
+            let mut pvs = AlphaBetaSearch::new(5, false).with_seed(seed as u64);
+            let mut plain = AlphaBetaSearch::new(5, false).with_seed(seed as u64);
+            plain.set_principal_variation_search(false);
+
+            let expected = plain.search(state, Player::Player1);
+            let result = pvs.search(state, Player::Player1);
+            assert!((result.evaluation - expected.evaluation).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_search_multipv_ranks_root_moves() {
+        let state = create_test_state();
+        let mut search = AlphaBetaSearch::new(3, false).with_seed(GOLDEN_SEED);
+
+        let ranked = search.search_multipv(&state, Player::Player1, 5);
+        assert_eq!(ranked.len(), 5);
+        for pair in ranked.windows(2) {
+            assert!(pair[0].evaluation >= pair[1].evaluation);
+        }
+        for result in &ranked {
+            assert_eq!(result.principal_variation.first(), Some(&result.best_move));
+            assert!(result.nodes_explored > 0);
+        }
+
+        // The top move is the one a plain search picks, and ties rank stably
+        let best = AlphaBetaSearch::new(3, false).with_seed(GOLDEN_SEED).search(&state, Player::Player1);
+        assert_eq!(ranked[0].evaluation, best.evaluation);
+        let again = search.search_multipv(&state, Player::Player1, 5);
+        let moves = |results: &[SearchResult]| results.iter().map(|r| r.best_move.clone()).collect::<Vec<_>>();
+        assert_eq!(moves(&again), moves(&ranked));
+
+        assert_eq!(search.search_multipv(&state, Player::Player1, 100).len(), 16);
+    }
+
+    #[test]
+    fn test_depth_reached_stops_at_game_end() {
+        // The claim and its answer end the game at the round limit
+        let mut state = create_test_state();
+        state.round = 19;
+
+        let mut serial = AlphaBetaSearch::new(5, false).with_seed(GOLDEN_SEED);
+        assert_eq!(serial.search(&state, Player::Player1).depth_reached, 2);
+        assert_eq!(serial.search_node_budgeted(&state, Player::Player1, 10_000).depth_reached, 2);
+
+        let mut parallel = AlphaBetaSearch::new(5, true).with_seed(GOLDEN_SEED);
+        assert_eq!(parallel.search(&state, Player::Player1).depth_reached, 2);
+
+        // Far from the end, the full depth is reached
+        assert_eq!(serial.search(&create_test_state(), Player::Player1).depth_reached, 5);
+    }
+
+    /// Evaluations of seeded searches covering the features that care
+    /// which side is to move: chance modes, transpositions, opponent models
+    /// and profiles, discounting, null moves and the parallel root split
+    fn side_sensitive_evaluations() -> Vec<f64> {
+        let mut challenge = create_test_state();
+        challenge.phase = Phase::Challenge;
+        challenge.current_claim = Some(crate::Claim {
+            description: "claim".to_string(),
+            claim_type: crate::ClaimType::Prediction,
+            boldness: 0.6,
+            is_bluff: true,
+        });
+        let mut ahead = create_test_state();
+        ahead.player1_trust = 70;
+        ahead.player2_trust = 45;
+
+        let seeded = |depth: u8| AlphaBetaSearch::new(depth, false).with_seed(GOLDEN_SEED);
+        let mut values = Vec::new();
+
+        for depth in 1..=5 {
+            values.push(seeded(depth).search(&create_test_state(), Player::Player1).evaluation);
+            let mut minimax = MinimaxSearch::new(depth.min(4)).with_seed(GOLDEN_SEED);
+            values.push(minimax.search(&ahead, Player::Player2).evaluation);
+        }
+        values.push(seeded(4).search(&challenge, Player::Player2).evaluation);
+        values.push(seeded(5).search(&ahead, Player::Player2).evaluation);
+        values.push(AlphaBetaSearch::new(5, true).with_seed(GOLDEN_SEED).search(&ahead, Player::Player1).evaluation);
+        values.push(seeded(5).with_transposition_table(4096).search(&ahead, Player::Player1).evaluation);
+        values.push(seeded(4).search_maximin_chance(&challenge, Player::Player2, 4).evaluation);
+        values.push(seeded(4).with_opponent_model(OpponentModel::new(0.6)).search(&ahead, Player::Player1).evaluation);
+        let mut timid = seeded(4).with_opponent_boldness(BoldnessProfile::Timid);
+        values.push(timid.search(&ahead, Player::Player2).evaluation);
+        values.push(seeded(4).with_risk_profile(RiskProfile::Aggressive).search(&ahead, Player::Player2).evaluation);
+
+        let mut discounted = seeded(4);
+        discounted.set_discount(0.9);
+        values.push(discounted.search(&challenge, Player::Player2).evaluation);
+
+        let mut null_moves = seeded(6);
+        null_moves.set_null_move(true);
+        values.push(null_moves.search(&ahead, Player::Player1).evaluation);
+
+        let mut expected = seeded(3);
+        expected.chance_mode = ChanceMode::Expected;
+        let scored = expected.search_scored(&ahead, Player::Player1, 3);
+        values.push(scored.iter().map(|(_, eval)| *eval).fold(f64::NEG_INFINITY, f64::max));
+        values.push(scored.iter().map(|(_, eval)| *eval).fold(f64::INFINITY, f64::min));
+        let strategy = expected.equilibrium_strategy(&ahead, Player::Player1, 3, 200);
+        values.push(strategy.iter().map(|(_, p)| *p).fold(0.0, f64::max));
+        values
+    }
+
+    #[test]
+    fn test_negamax_matches_recorded_values() {
+        // Recorded with the separate maximizing and minimizing branches the
+        // searches had before negamax. Since then, minimax settles claims
+        // pending at its leaves (the odd depths below) and alpha-beta
+        // discounts those replies a ply further (the discounted search).
+        let recorded = [
+            2.8333333333333335, -5.5, 2.8333333333333335, -5.5, 5.666666666666667,
+            -2.6666666666666665, 5.666666666666667, -2.6666666666666665, 8.5, -2.6666666666666665,
+            -7.333333333333334, 0.16666666666666652, 26.833333333333336, 26.833333333333336,
+            -7.333333333333334, 24.781866666666666, -2.6666666666666665, 2.3333333333333335,
+            -4.330260000000002, 26.833333333333336, 9.693333333333335, 6.633333333333334, 0.24875,
+        ];
+
+        assert_eq!(side_sensitive_evaluations(), recorded);
+    }
+
+    #[test]
+    fn test_golden_node_counts() {
+        let state = create_test_state();
+
+        assert_eq!(search_node_count(&state, 3), 759);
+        assert_eq!(search_node_count(&state, 4), 620);
+        assert_eq!(search_node_count(&state, 5), 12023);
+    }
+
+    #[test]
+    fn test_short_round_limit_ends_search_earlier() {
+        let mut state = create_test_state();
+        state.round = 3;
+        let short = GameRules { max_round: 5, ..GameRules::default() };
+
+        let mut standard = AlphaBetaSearch::new(6, false).with_seed(GOLDEN_SEED);
+        let mut limited = AlphaBetaSearch::new(6, false).with_rules(short).with_seed(GOLDEN_SEED);
+        let standard_result = standard.search(&state, Player::Player1);
+        let limited_result = limited.search(&state, Player::Player1);
+
+        // Two claim-and-response rounds fit before the limit, so nothing
+        // below four plies is ever expanded
+        assert!(limited_result.nodes_explored < standard_result.nodes_explored);
+        assert_eq!(limited.plies_to_terminal(&state, Player::Player1, 6), Some(4));
+    }
+
+    #[test]
+    fn test_maximin_avoids_risky_challenge() {
+        // A bold claim is likely refuted, so challenging it pays off on average,
+        // but an upheld claim costs the challenger far more than accepting
+        let mut state = create_test_state();
+        state.phase = Phase::Challenge;
+        state.current_claim = Some(crate::Claim {
+            description: "Bold claim".to_string(),
+            claim_type: crate::ClaimType::Accusation,
+            boldness: 0.8,
+            is_bluff: true,
+        });
+
+        let mut search = AlphaBetaSearch::new(1, false);
+        let expected = search.search_with_chance_mode(&state, Player::Player2, 1, ChanceMode::Expected);
+        let maximin = search.search_maximin_chance(&state, Player::Player2, 1);
+
+        assert_eq!(expected.best_move.action, crate::Action::Challenge);
+        assert_eq!(maximin.best_move.action, crate::Action::Accept);
+        assert!(maximin.evaluation < expected.evaluation);
+    }
+
+    #[test]
+    fn test_expected_value_lies_between_outcomes() {
+        let mut state = create_test_state();
+        state.phase = Phase::Challenge;
+        state.current_claim = Some(crate::Claim {
+            description: "Bold claim".to_string(),
+            claim_type: crate::ClaimType::Accusation,
+            boldness: 0.8,
+            is_bluff: true,
+        });
+
+        let mut search = AlphaBetaSearch::new(1, false);
+        let result = search.search_with_chance_mode(&state, Player::Player2, 1, ChanceMode::Expected);
+        assert_eq!(result.best_move.action, Action::Challenge);
+
+        let tree = GameTree::new(state.clone());
+        let values: Vec<f64> = tree
+            .resolution_outcomes(&state, &result.best_move)
+            .iter()
+            .map(|(outcome, _)| search.evaluator.evaluate(outcome, Player::Player2))
+            .collect();
+        let low = values.iter().cloned().fold(f64::INFINITY, f64::min);
+        let high = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+
+        assert_eq!(values.len(), 2);
+        assert!(low < result.evaluation && result.evaluation < high);
+    }
+
+    #[test]
+    fn test_quiescence_settles_pending_challenge() {
+        // A bluff awaits player 2's reply: challenging it is likely to win 15
+        let mut state = create_test_state();
+        state.phase = Phase::Challenge;
+        state.current_claim = Some(crate::Claim {
+            description: "Bold claim".to_string(),
+            claim_type: crate::ClaimType::Accusation,
+            boldness: 0.8,
+            is_bluff: true,
+        });
+
+        let mut search = AlphaBetaSearch::new(0, false);
+        let settled = search.search_with_chance_mode(&state, Player::Player2, 0, ChanceMode::Expected);
+        let naive = search.evaluator.evaluate(&state, Player::Player2);
+
+        assert!(settled.nodes_explored > 1);
+        assert!(settled.evaluation > naive);
+    }
+
+    #[test]
+    fn test_null_move_pruning() {
+        // Moving is nearly always an advantage in this game, so passing
+        // rarely fails high; it takes a deep search to see it happen
+        let mut state = create_test_state();
+        state.player1_trust = 40;
+        state.player2_trust = 60;
+
+        let mut plain = AlphaBetaSearch::new(7, false).with_seed(GOLDEN_SEED);
+        let mut pruned = AlphaBetaSearch::new(7, false).with_seed(GOLDEN_SEED);
+        pruned.set_null_move(true);
+
+        let plain_result = plain.search(&state, Player::Player1);
+        let pruned_result = pruned.search(&state, Player::Player1);
+
+        assert_eq!(plain_result.null_move_cutoffs, 0);
+        assert!(pruned_result.null_move_cutoffs > 0);
+        assert_eq!(pruned_result.best_move, plain_result.best_move);
+
+        // One challenge away from winning, passing is never tried
+        let mut close = create_test_state();
+        close.player1_trust = 90;
+        assert!(pruned.null_move_allowed(&state, 4, 2));
+        assert!(!pruned.null_move_allowed(&close, 4, 2));
+        assert!(!plain.null_move_allowed(&state, 4, 2));
+    }
+
+    #[test]
+    fn test_tiny_cache_evicts_and_stays_correct() {
+        let state = create_test_state();
+
+        let mut uncached = AlphaBetaSearch::new(3, false);
+        let reference = uncached.search_with_chance_mode(&state, Player::Player1, 3, ChanceMode::Expected);
+
+        let mut cached = AlphaBetaSearch::new(3, false).with_cache_capacity(4);
+        let result = cached.search_with_chance_mode(&state, Player::Player1, 3, ChanceMode::Expected);
+        let stats = cached.cache_stats();
+
+        assert_eq!(result.evaluation, reference.evaluation);
+        assert_eq!(stats.capacity, 4);
+        assert!(stats.occupancy <= 4);
+        assert!(stats.misses > 4); // more distinct leaves than slots, so entries were evicted
+    }
+
+    #[test]
+    fn test_repeated_position_scores_zero() {
+        // Challenges never settle anything and keep the round, so the
+        // claimer's opponent can always send the game back to the start
+        let endless = GameRules {
+            inconclusive_probability: 1.0,
+            ..GameRules::default()
+        };
+        let mut state = create_test_state();
+        state.player1_trust = 60;
+
+        // Back at the start once: not yet a repetition draw
+        let mut shallow = AlphaBetaSearch::new(2, false).with_rules(endless);
+        assert_ne!(shallow.search(&state, Player::Player1).evaluation, 0.0);
+
+        // The third time the start comes up, the line is a draw
+        let mut deep = AlphaBetaSearch::new(4, false).with_rules(endless);
+        let result = deep.search(&state, Player::Player1);
+        assert_eq!(result.evaluation, 0.0);
+        assert_eq!(result.principal_variation.len(), 4);
+    }
+
+    #[test]
+    fn test_repetition_counts_the_game_history() {
+        let endless = GameRules {
+            inconclusive_probability: 1.0,
+            ..GameRules::default()
+        };
+        let tree = GameTree::new_with_rules(create_test_state(), endless);
+        let mut state = create_test_state();
+        state.player1_trust = 60;
+
+        // The start has come up twice and the same claim is pending again
+        let claim = tree.generate_moves(&state, Player::Player1).swap_remove(0);
+        let challenge = Move {
+            action: crate::Action::Challenge,
+            player: Player::Player2,
+            claim: None,
+            confidence: 0.5,
+            trust_after: None,
+        };
+        for played in [&claim, &challenge, &claim] {
+            tree.make_move(&mut state, played);
+        }
+
+        let challenge_value = |state: &GameState| {
+            AlphaBetaSearch::new(3, false)
+                .with_rules(endless)
+                .search_multipv(state, Player::Player2, 2)
+                .into_iter()
+                .find(|result| result.best_move.action == crate::Action::Challenge)
+                .unwrap()
+                .evaluation
+        };
+
+        // Challenging brings the start up a third time
+        assert_eq!(challenge_value(&state), 0.0);
+
+        // Without the history it is only the second time
+        state.move_history.clear();
+        assert_ne!(challenge_value(&state), 0.0);
+    }
+
+    #[test]
+    fn test_challenge_extension_searches_deeper() {
+        let state = create_test_state();
+
+        let mut uniform = AlphaBetaSearch::new(2, false).with_seed(GOLDEN_SEED);
+        let plain = uniform.search(&state, Player::Player1);
+        assert_eq!(plain.principal_variation.len(), 2);
+
+        // Both claims on the line are free, so two more plies are searched
+        let mut extended = AlphaBetaSearch::new(2, false)
+            .with_seed(GOLDEN_SEED)
+            .with_depth_policy(DepthPolicy::ExtendChallenges { max_extensions: 4 });
+        let result = extended.search(&state, Player::Player1);
+        assert_eq!(result.principal_variation.len(), 4);
+        assert!(result.nodes_explored > plain.nodes_explored);
+
+        // Extensions run out after `max_extensions` claims
+        let mut capped = AlphaBetaSearch::new(2, false)
+            .with_seed(GOLDEN_SEED)
+            .with_depth_policy(DepthPolicy::ExtendChallenges { max_extensions: 1 });
+        assert_eq!(capped.search(&state, Player::Player1).principal_variation.len(), 3);
+    }
+
+    #[test]
+    fn test_nan_weight_still_finds_a_move() {
+        let weights = crate::EvaluationWeights::builder().trust_differential(f64::NAN).build();
+        let state = create_test_state();
+
+        for parallel in [false, true] {
+            let mut search = AlphaBetaSearch::new(4, parallel)
+                .with_evaluator(crate::Evaluator::with_weights(weights.clone()))
+                .with_seed(GOLDEN_SEED);
+            let result = search.search(&state, Player::Player1);
+
+            assert!(result.evaluation.is_finite());
+            assert!(GameTree::new(state.clone()).is_legal(&state, &result.best_move));
+        }
+    }
+
+    #[test]
+    fn test_drawn_terminal_scores_zero() {
+        let mut state = create_test_state();
+        state.round = 20;
+        state.player1_trust = 55;
+
+        let lenient = GameRules { draw_margin: 10, ..GameRules::default() };
+        let mut drawn = AlphaBetaSearch::new(2, false).with_rules(lenient);
+        let mut decided = AlphaBetaSearch::new(2, false);
+
+        assert_eq!(drawn.search(&state, Player::Player1).evaluation, 0.0);
+        assert!(decided.search(&state, Player::Player1).evaluation > 0.0);
+    }
+
+    #[test]
+    fn test_decision_criticality() {
+        let mut search = AlphaBetaSearch::new(3, false).with_seed(GOLDEN_SEED);
+        search.chance_mode = ChanceMode::Expected;
+
+        // Accepting hands player 1 the win, while a refuted challenge wins it
+        // for player 2 and an upheld one leaves player 2 far behind
+        let mut critical = create_test_state();
+        critical.phase = Phase::Challenge;
+        critical.player1_trust = 95;
+        critical.player2_trust = 90;
+        critical.current_claim = Some(crate::Claim {
+            description: "bold".to_string(),
+            claim_type: crate::ClaimType::Accusation,
+            boldness: 0.8,
+            is_bluff: true,
+        });
+
+        let high = search.decision_criticality(&critical, Player::Player2, 1);
+        let low = search.decision_criticality(&create_test_state(), Player::Player1, 2);
+        assert!(low < 3.5);
+        assert!(high > 4.0 * low);
+    }
+
+    #[test]
+    fn test_confidence_weight_shifts_near_tie() {
+        // Accepting is worth slightly more than challenging here, but a
+        // challenge is played with more confidence
+        let mut state = create_test_state();
+        state.phase = Phase::Challenge;
+        state.player1_trust = -10;
+        state.player2_trust = 10;
+        state.current_claim = Some(crate::Claim {
+            description: "bold".to_string(),
+            claim_type: crate::ClaimType::Prediction,
+            boldness: 0.8,
+            is_bluff: true,
+        });
+
+        let mut search = AlphaBetaSearch::new(1, false);
+        search.chance_mode = ChanceMode::Expected;
+        assert_eq!(search.search(&state, Player::Player2).best_move.action, Action::Accept);
+
+        search.set_confidence_weight(1.0);
+        assert_eq!(search.search(&state, Player::Player2).best_move.action, Action::Challenge);
+    }
+
+    #[test]
+    fn test_search_timed_respects_budget() {
+        let state = create_test_state();
+        let budget = Duration::from_millis(20);
+
+        let mut search = AlphaBetaSearch::new(60, false);
+        search.chance_mode = ChanceMode::Expected;
+        let start = Instant::now();
+        let result = search.search_timed(&state, Player::Player1, budget);
+
+        assert!(start.elapsed() < budget * 5);
+        assert!(result.depth_reached >= 1);
+        assert_eq!(result.best_move.action, Action::MakeClaim);
+    }
+
+    #[test]
+    fn test_search_timed_without_budget_still_finds_a_move() {
+        let state = create_test_state();
+        let mut search = AlphaBetaSearch::new(6, false).with_seed(1);
+
+        let result = search.search_timed(&state, Player::Player1, Duration::ZERO);
+
+        assert_eq!(result.best_move.action, Action::MakeClaim);
+        assert!(result.depth_reached >= 1);
         assert!(result.nodes_explored > 0);
     }
+
+    #[test]
+    fn test_expired_deadline_keeps_best_root_move() {
+        let state = create_test_state();
+
+        // Simulate the clock running out partway through depth 1
+        let mut search = AlphaBetaSearch::new(3, false);
+        search.deadline = Some(Instant::now());
+        search.nodes_explored = TIME_CHECK_INTERVAL - 4;
+        let tree = search.build_tree(&state, Player::Player1);
+        let (best_move, _) = search.alpha_beta(
+            &tree,
+            &mut state.clone(),
+            1,
+            f64::NEG_INFINITY,
+            f64::INFINITY,
+            Player::Player1,
+            true,
+        );
+
+        assert!(search.aborted);
+        assert_eq!(best_move.unwrap().action, Action::MakeClaim);
+        // The clock is read, and the search abandoned, at the third claim
+        assert_eq!(search.nodes_explored, TIME_CHECK_INTERVAL);
+    }
+
+    #[test]
+    fn test_plies_to_terminal() {
+        // Under these thresholds every answer to the claim ends the game:
+        // accepting lifts player 1 to the win, and a challenge either
+        // wins it for player 2 or drops them to the losing threshold
+        let rules = GameRules {
+            win_trust: 55,
+            lose_trust: 40,
+            ..GameRules::default()
+        };
+        let mut state = create_test_state();
+        state.phase = Phase::Challenge;
+        state.current_claim = Some(crate::Claim {
+            description: "claim".to_string(),
+            claim_type: crate::ClaimType::Information,
+            boldness: 0.4,
+            is_bluff: false,
+        });
+
+        let mut search = AlphaBetaSearch::new(4, false).with_rules(rules).with_seed(GOLDEN_SEED);
+        assert_eq!(search.plies_to_terminal(&state, Player::Player2, 4), Some(1));
+        assert_eq!(search.pv_table[0].moves.len(), 1);
+
+        let mut opening = AlphaBetaSearch::new(2, false).with_seed(GOLDEN_SEED);
+        assert_eq!(opening.plies_to_terminal(&create_test_state(), Player::Player1, 2), None);
+    }
+
+    #[test]
+    fn test_diversity_penalty_varies_claim_types() {
+        let distinct_types = |penalty: f64| {
+            let mut state = create_test_state();
+            let mut search = AlphaBetaSearch::new(1, false);
+            search.set_diversity_penalty(penalty, 3);
+
+            let mut types = Vec::new();
+            // Expected outcomes value every claim type alike, so only the
+            // penalty can tell them apart
+            for _ in 0..4 {
+                let best_move = search.search_with_chance_mode(&state, Player::Player1, 1, ChanceMode::Expected).best_move;
+                let claim_type = best_move.claim.as_ref().unwrap().claim_type;
+                if !types.contains(&claim_type) {
+                    types.push(claim_type);
+                }
+                state.move_history.push(best_move);
+            }
+            types.len()
+        };
+
+        assert_eq!(distinct_types(0.0), 1);
+        assert_eq!(distinct_types(1.0), 4);
+    }
+
+    #[test]
+    fn test_estimated_memory_scales() {
+        let entry = LruCache::<(u64, Player), f64>::entry_bytes();
+
+        let small = AlphaBetaSearch::new(6, false).with_cache_capacity(1_000).estimated_memory_bytes();
+        let large = AlphaBetaSearch::new(6, false).with_cache_capacity(11_000).estimated_memory_bytes();
+        assert_eq!(large - small, 10_000 * entry);
+
+        let serial = AlphaBetaSearch::new(6, false).estimated_memory_bytes();
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(4).build().unwrap();
+        let parallel = pool.install(|| AlphaBetaSearch::new(6, true).estimated_memory_bytes());
+        assert_eq!(parallel, 4 * serial);
+
+        assert!(AlphaBetaSearch::new(12, false).estimated_memory_bytes() > serial);
+    }
+
+    #[test]
+    fn test_mate_distance_prefers_shorter_win() {
+        // Player 2 wins on the lead whenever the game reaches round 20.
+        // Accepting gets there at once; an inconclusive challenge keeps
+        // round 19 and wins only after another claim and reply.
+        let rules = GameRules {
+            win_trust: 1000,
+            lose_trust: -1000,
+            inconclusive_probability: 1.0,
+            ..GameRules::default()
+        };
+        let mut state = create_test_state();
+        state.round = 19;
+        state.phase = Phase::Challenge;
+        state.player1_trust = 10;
+        state.player2_trust = 80;
+        state.current_claim = Some(crate::Claim {
+            description: "claim".to_string(),
+            claim_type: crate::ClaimType::Information,
+            boldness: 0.5,
+            is_bluff: false,
+        });
+
+        let mut search = AlphaBetaSearch::new(4, false).with_rules(rules);
+        let result = search.search(&state, Player::Player2);
+        assert_eq!(result.best_move.action, Action::Accept);
+        assert_eq!(result.evaluation, MATE_SCORE - 1.0);
+
+        // Seen from the loser, the same game is lost as slowly as it can be
+        let challenge = Move {
+            action: Action::Challenge,
+            player: Player::Player2,
+            claim: None,
+            confidence: 0.5,
+            trust_after: None,
+        };
+        let later = GameTree::new_with_rules(state.clone(), rules).apply_move(&state, &challenge).unwrap();
+        let mut search = AlphaBetaSearch::new(4, false).with_rules(rules);
+        assert_eq!(search.search(&later, Player::Player1).evaluation, 2.0 - MATE_SCORE);
+    }
+
+    #[test]
+    fn test_discount_prefers_faster_win() {
+        // With these thresholds every reply to a claim ends the game, so the
+        // same kind of finish is one ply away from the challenge phase and
+        // two plies away from the claim phase
+        let rules = GameRules {
+            win_trust: 55,
+            lose_trust: 40,
+            ..GameRules::default()
+        };
+        let claim = crate::Claim {
+            description: "claim".to_string(),
+            claim_type: crate::ClaimType::Information,
+            boldness: 0.4,
+            is_bluff: false,
+        };
+        let mut fast = create_test_state();
+        fast.phase = Phase::Challenge;
+        fast.current_claim = Some(claim.clone());
+        let mut slow = create_test_state();
+        slow.move_history.push(Move {
+            action: Action::MakeClaim,
+            player: Player::Player2,
+            claim: Some(claim),
+            confidence: 0.88,
+            trust_after: None,
+        });
+
+        let value = |state: &GameState, player: Player, gamma: f64| {
+            let mut search = AlphaBetaSearch::new(4, false).with_rules(rules).with_seed(GOLDEN_SEED);
+            search.chance_mode = ChanceMode::Expected;
+            search.set_discount(gamma);
+            search.search(state, player)
+        };
+
+        // Player 2 answering now versus player 1 claiming first: player 2's
+        // finish is worth less, and player 1's more, the later it comes
+        let undiscounted = value(&fast, Player::Player2, 1.0).evaluation;
+        let discounted = value(&fast, Player::Player2, 0.9).evaluation;
+        assert!((discounted - undiscounted * 0.9).abs() < 1e-9);
+        let slow_undiscounted = value(&slow, Player::Player1, 1.0).evaluation;
+        let slow_discounted = value(&slow, Player::Player1, 0.9).evaluation;
+        assert!((slow_discounted - slow_undiscounted * 0.81).abs() < 1e-9);
+
+        // A gamma of 1 is exactly the undiscounted search
+        let plain = AlphaBetaSearch::new(4, false).with_seed(GOLDEN_SEED).search(&create_test_state(), Player::Player1);
+        let mut gamma_one = AlphaBetaSearch::new(4, false).with_seed(GOLDEN_SEED);
+        gamma_one.set_discount(1.0);
+        let gamma_one = gamma_one.search(&create_test_state(), Player::Player1);
+        assert_eq!(plain.evaluation, gamma_one.evaluation);
+        assert_eq!(plain.nodes_explored, gamma_one.nodes_explored);
+    }
+
+    #[test]
+    fn test_no_move_policy() {
+        // Nothing can be played in the resolution phase of an unfinished game
+        let mut state = create_test_state();
+        state.phase = Phase::Resolution;
+        state.player1_trust = 70;
+
+        let mut pass = AlphaBetaSearch::new(3, false);
+        let result = pass.search(&state, Player::Player1);
+        assert_eq!(result.evaluation, Evaluator::new().evaluate(&state, Player::Player1));
+        // The root and one pass per remaining ply
+        assert_eq!(result.nodes_explored, 4);
+
+        let stalemate_rules = GameRules {
+            no_move_policy: NoMovePolicy::Stalemate,
+            ..GameRules::default()
+        };
+        let mut stalemate = AlphaBetaSearch::new(3, false).with_rules(stalemate_rules);
+        let result = stalemate.search(&state, Player::Player1);
+        assert_eq!(result.evaluation, 0.0);
+        assert_eq!(result.nodes_explored, 1);
+    }
+
+    #[test]
+    fn test_equilibrium_strategy_is_distribution() {
+        let mut search = AlphaBetaSearch::new(3, false);
+        search.chance_mode = ChanceMode::Expected;
+        let state = create_test_state();
+
+        let strategy = search.equilibrium_strategy(&state, Player::Player1, 3, 500);
+        let total: f64 = strategy.iter().map(|(_, p)| p).sum();
+
+        assert_eq!(strategy.len(), 16);
+        assert!((total - 1.0).abs() < 1e-9);
+        assert!(strategy.iter().all(|(_, p)| *p >= 0.0));
+    }
+
+    #[test]
+    fn test_equilibrium_strategy_collapses_on_dominant_move() {
+        let mut state = create_test_state();
+        state.phase = Phase::Challenge;
+        state.current_claim = Some(crate::Claim {
+            description: "Bold claim".to_string(),
+            claim_type: crate::ClaimType::Accusation,
+            boldness: 0.8,
+            is_bluff: true,
+        });
+
+        let mut search = AlphaBetaSearch::new(1, false);
+        search.chance_mode = ChanceMode::Expected;
+        let strategy = search.equilibrium_strategy(&state, Player::Player2, 1, 1_000);
+
+        let (best_move, probability) = strategy
+            .iter()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap();
+        assert_eq!(best_move.action, crate::Action::Challenge);
+        assert!(*probability > 0.99);
+    }
+
+    #[test]
+    fn test_claim_levels_replace_profile_levels() {
+        let levels = ClaimLevels::new(vec![0.3], 0.2).unwrap();
+        let state = create_test_state();
+
+        for parallel in [false, true] {
+            let mut search = AlphaBetaSearch::new(4, parallel)
+                .with_seed(GOLDEN_SEED)
+                .with_risk_profile(RiskProfile::Aggressive)
+                .with_claim_levels(levels.clone());
+            let result = search.search(&state, Player::Player1);
+
+            let claims: Vec<&crate::Claim> = result.principal_variation.iter().filter_map(|m| m.claim.as_ref()).collect();
+            assert!(!claims.is_empty());
+            assert!(claims.iter().all(|claim| claim.boldness == 0.3 && claim.is_bluff));
+        }
+    }
+
+    #[test]
+    fn test_accept_limit_forces_challenge() {
+        let mut state = create_test_state();
+        state.phase = Phase::Challenge;
+        state.current_claim = Some(crate::Claim {
+            description: "Bold claim".to_string(),
+            claim_type: crate::ClaimType::Accusation,
+            boldness: 0.8,
+            is_bluff: false,
+        });
+
+        let mut open = AlphaBetaSearch::new(3, false).with_seed(GOLDEN_SEED);
+        assert_eq!(open.search_multipv(&state, Player::Player2, 2).len(), 2);
+
+        let mut limited = AlphaBetaSearch::new(3, false).with_seed(GOLDEN_SEED).with_accept_limit(0.5);
+        let results = limited.search_multipv(&state, Player::Player2, 2);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].best_move.action, Action::Challenge);
+    }
 }
@@ -0,0 +1,87 @@
+use crate::{GameState, Player, SearchResult};
+use serde::{Deserialize, Serialize};
+use crate::time::{SystemTime, UNIX_EPOCH};
+
+/// A search result bundled with what it was computed for, so that it can be
+/// stored in an external cache keyed by `state_key`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedAnalysis {
+    pub state_key: u64,
+    pub player: Player,
+    pub depth: u8,
+    pub result: SearchResult,
+    /// Seconds since the Unix epoch when the analysis was made
+    pub timestamp: u64,
+}
+
+impl CachedAnalysis {
+    pub fn new(state: &GameState, player: Player, depth: u8, result: SearchResult) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or(0);
+
+        Self {
+            state_key: state.state_key(),
+            player,
+            depth,
+            result,
+            timestamp,
+        }
+    }
+
+    /// Whether this analysis answers a request for `state` and `player`
+    pub fn matches(&self, state: &GameState, player: Player) -> bool {
+        self.state_key == state.state_key() && self.player == player
+    }
+
+    /// Whether a request for `depth` needs a deeper search than this one
+    pub fn is_stale(&self, depth: u8) -> bool {
+        self.depth < depth
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AlphaBetaSearch, Phase};
+
+    fn create_test_state() -> GameState {
+        GameState {
+            round: 1,
+            phase: Phase::Claim,
+            player1_trust: 50,
+            player2_trust: 50,
+            current_claim: None,
+            move_history: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_cached_analysis_round_trip() {
+        let state = create_test_state();
+        let mut search = AlphaBetaSearch::new(4, false).with_seed(7);
+        let analysis = search.analyze(&state, Player::Player1, 3);
+
+        let json = serde_json::to_string(&analysis).unwrap();
+        let restored: CachedAnalysis = serde_json::from_str(&json).unwrap();
+
+        assert!(restored.matches(&state, Player::Player1));
+        assert!(!restored.matches(&state, Player::Player2));
+        assert_eq!(restored.depth, 3);
+        assert_eq!(restored.timestamp, analysis.timestamp);
+        assert_eq!(restored.result.evaluation, analysis.result.evaluation);
+        assert_eq!(restored.result.nodes_explored, analysis.result.nodes_explored);
+    }
+
+    #[test]
+    fn test_shallower_analysis_is_stale() {
+        let state = create_test_state();
+        let mut search = AlphaBetaSearch::new(4, false).with_seed(7);
+        let analysis = search.analyze(&state, Player::Player1, 4);
+
+        assert!(!analysis.is_stale(4));
+        assert!(!analysis.is_stale(3));
+        assert!(analysis.is_stale(6));
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+/// Bounded cache that evicts the least recently used entry when full.
+/// A capacity of 0 disables caching entirely.
+#[derive(Debug, Clone)]
+pub struct LruCache<K, V> {
+    capacity: usize,
+    entries: HashMap<K, (V, u64)>,
+    recency: BTreeMap<u64, K>,
+    tick: u64,
+    hits: u64,
+    misses: u64,
+}
+
+/// Snapshot of cache effectiveness and occupancy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub occupancy: usize,
+    pub capacity: usize,
+}
+
+impl CacheStats {
+    /// Share of lookups that were hits, 0 before any lookup
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            return 0.0;
+        }
+
+        self.hits as f64 / lookups as f64
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> LruCache<K, V> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::with_capacity(capacity),
+            recency: BTreeMap::new(),
+            tick: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Look up a value, marking it as most recently used
+    pub fn get(&mut self, key: &K) -> Option<V> {
+        if self.capacity == 0 {
+            return None;
+        }
+
+        self.tick += 1;
+        let tick = self.tick;
+
+        match self.entries.get_mut(key) {
+            Some((value, last_used)) => {
+                self.recency.remove(last_used);
+                self.recency.insert(tick, key.clone());
+                *last_used = tick;
+                self.hits += 1;
+                Some(value.clone())
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Insert a value, evicting the least recently used entry if full
+    pub fn insert(&mut self, key: K, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        self.tick += 1;
+
+        if let Some((_, last_used)) = self.entries.remove(&key) {
+            self.recency.remove(&last_used);
+        } else if self.entries.len() >= self.capacity {
+            if let Some((_, oldest)) = self.recency.pop_first() {
+                self.entries.remove(&oldest);
+            }
+        }
+
+        self.recency.insert(self.tick, key.clone());
+        self.entries.insert(key, (value, self.tick));
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Approximate bytes used per entry: the key and value in the map plus
+    /// the key again in the recency index, with a rough allowance for the
+    /// collections' own bookkeeping
+    pub fn entry_bytes() -> usize {
+        let map_entry = std::mem::size_of::<(K, (V, u64))>();
+        let recency_entry = std::mem::size_of::<(u64, K)>();
+        (map_entry + recency_entry) * 3 / 2
+    }
+
+    /// Approximate bytes used when the cache is full
+    pub fn estimated_bytes(&self) -> usize {
+        self.capacity * Self::entry_bytes()
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits,
+            misses: self.misses,
+            occupancy: self.entries.len(),
+            capacity: self.capacity,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lru_evicts_least_recently_used() {
+        let mut cache = LruCache::new(2);
+        cache.insert(1, "a");
+        cache.insert(2, "b");
+
+        // Touch 1 so that 2 becomes the eviction candidate
+        assert_eq!(cache.get(&1), Some("a"));
+        cache.insert(3, "c");
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&2), None);
+        assert_eq!(cache.get(&1), Some("a"));
+        assert_eq!(cache.get(&3), Some("c"));
+    }
+
+    #[test]
+    fn test_zero_capacity_disables_cache() {
+        let mut cache = LruCache::new(0);
+        cache.insert(1, 1.0);
+
+        assert!(cache.is_empty());
+        assert_eq!(cache.get(&1), None);
+        assert_eq!(cache.stats().misses, 0);
+    }
+}
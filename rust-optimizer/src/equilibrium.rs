@@ -0,0 +1,89 @@
+//! Mixed-strategy equilibrium approximation for zero-sum matrix games
+
+/// Approximate the row player's equilibrium strategy for a zero-sum game.
+///
+/// `payoffs[i][j]` is the row player's payoff when row `i` meets column `j`;
+/// the column player tries to minimize it. Both sides run regret matching
+/// against each other and the row player's average strategy is returned,
+/// which converges to a Nash equilibrium as `iterations` grows.
+/// All rows must have the same number of columns.
+pub fn regret_matching(payoffs: &[Vec<f64>], iterations: usize) -> Vec<f64> {
+    let rows = payoffs.len();
+    if rows == 0 {
+        return Vec::new();
+    }
+
+    let columns = payoffs[0].len();
+    if columns == 0 || iterations == 0 {
+        return vec![1.0 / rows as f64; rows];
+    }
+
+    let mut row_regret = vec![0.0; rows];
+    let mut column_regret = vec![0.0; columns];
+    let mut strategy_sum = vec![0.0; rows];
+
+    for _ in 0..iterations {
+        let row_strategy = strategy_from_regret(&row_regret);
+        let column_strategy = strategy_from_regret(&column_regret);
+
+        let row_values: Vec<f64> = payoffs
+            .iter()
+            .map(|row| row.iter().zip(&column_strategy).map(|(p, q)| p * q).sum())
+            .collect();
+        let game_value: f64 = row_values.iter().zip(&row_strategy).map(|(v, p)| v * p).sum();
+
+        for (regret, value) in row_regret.iter_mut().zip(&row_values) {
+            *regret += value - game_value;
+        }
+
+        for (j, regret) in column_regret.iter_mut().enumerate() {
+            let column_value: f64 = payoffs
+                .iter()
+                .zip(&row_strategy)
+                .map(|(row, p)| row[j] * p)
+                .sum();
+            *regret += game_value - column_value;
+        }
+
+        for (sum, p) in strategy_sum.iter_mut().zip(&row_strategy) {
+            *sum += p;
+        }
+    }
+
+    let total: f64 = strategy_sum.iter().sum();
+    strategy_sum.iter().map(|sum| sum / total).collect()
+}
+
+/// Play each action in proportion to its positive regret, or uniformly if none
+fn strategy_from_regret(regret: &[f64]) -> Vec<f64> {
+    let positive: Vec<f64> = regret.iter().map(|r| r.max(0.0)).collect();
+    let total: f64 = positive.iter().sum();
+
+    if total > 0.0 {
+        positive.iter().map(|r| r / total).collect()
+    } else {
+        vec![1.0 / regret.len() as f64; regret.len()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matching_pennies_is_uniform() {
+        let payoffs = vec![vec![1.0, -1.0], vec![-1.0, 1.0]];
+        let strategy = regret_matching(&payoffs, 10_000);
+
+        assert!((strategy[0] - 0.5).abs() < 0.05);
+        assert!((strategy[1] - 0.5).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_dominant_row_is_pure() {
+        let payoffs = vec![vec![3.0, 2.0], vec![1.0, 0.0]];
+        let strategy = regret_matching(&payoffs, 1_000);
+
+        assert!(strategy[0] > 0.99);
+    }
+}
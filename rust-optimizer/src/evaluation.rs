@@ -1,17 +1,239 @@
-use crate::{GameState, Player};
+use crate::cache::{CacheStats, LruCache};
+use crate::{Action, GameState, GameTree, Phase, Player};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// Fixed-point scale used in integer mode: scores are in thousandths
+pub const FIXED_POINT_SCALE: i64 = 1000;
+
+/// Boldness from which a claim counts as bold for the over-acceptance term
+const BOLD_CLAIM: f64 = 0.6;
+
+/// Responses to bold claims needed before an accept rate means anything
+const MIN_BOLD_RESPONSES: usize = 3;
+
+/// Moves the momentum term looks back over
+const MOMENTUM_WINDOW: usize = 5;
+
+/// Upper bound on the tie-break jitter, far below any meaningful difference
+const MAX_JITTER: f64 = 1e-7;
+
+/// Upper bound on rollout length, in case a game never ends
+const MAX_ROLLOUT_PLIES: usize = 200;
+
+/// Scores game states for the searches, so they can run on any evaluation
+/// function, such as a learned one, instead of the built-in `Evaluator`.
+///
+/// ```
+/// use strategic_mind_optimizer::{AlphaBetaSearch, GameState, Phase, Player, StateEvaluator};
+///
+/// /// Scores a state by the raw trust differential and nothing else
+/// struct TrustDifferential;
+///
+/// impl StateEvaluator for TrustDifferential {
+///     fn evaluate(&self, state: &GameState, player: Player) -> f64 {
+///         let differential = (state.player1_trust - state.player2_trust) as f64;
+///         match player {
+///             Player::Player1 => differential,
+///             Player::Player2 => -differential,
+///         }
+///     }
+/// }
+///
+/// let state = GameState {
+///     round: 1,
+///     phase: Phase::Claim,
+///     player1_trust: 50,
+///     player2_trust: 50,
+///     current_claim: None,
+///     move_history: Vec::new(),
+/// };
+/// let mut search = AlphaBetaSearch::new(3, false).with_evaluator(TrustDifferential);
+/// let result = search.search(&state, Player::Player1);
+/// assert_eq!(result.best_move.player, Player::Player1);
+/// ```
+pub trait StateEvaluator: Send + Sync {
+    /// Value of `state` for `player`; higher is better for them
+    fn evaluate(&self, state: &GameState, player: Player) -> f64;
+}
+
+impl<E: StateEvaluator + ?Sized> StateEvaluator for Arc<E> {
+    fn evaluate(&self, state: &GameState, player: Player) -> f64 {
+        (**self).evaluate(state, player)
+    }
+}
+
+impl StateEvaluator for Evaluator {
+    fn evaluate(&self, state: &GameState, player: Player) -> f64 {
+        Evaluator::evaluate(self, state, player)
+    }
+}
 
 /// State evaluation function for game tree search
+#[derive(Debug, Clone)]
 pub struct Evaluator {
     weights: EvaluationWeights,
+    /// Blend between zero-sum play (1.0) and rewarding total trust (0.0)
+    competitiveness: f64,
+    integer_mode: bool,
+    tie_break_jitter: bool,
+    /// Per-round weights overriding `weights` where they apply
+    schedule: Option<WeightSchedule>,
+    /// Random playouts replacing the weighted terms, if enabled
+    rollouts: Option<Rollouts>,
+    /// Memoized evaluations, shared by every clone of this evaluator
+    cache: Option<Arc<Mutex<EvaluationCache>>>,
 }
 
+/// Evaluations of `Evaluator::with_cache`, by state key and player
+type EvaluationCache = LruCache<(u64, Player), f64>;
+
+/// Random playout settings of `Evaluator::with_rollouts`
+#[derive(Debug, Clone, Copy)]
+struct Rollouts {
+    count: u32,
+    seed: u64,
+}
+
+/// A game state whose trust values are only known to lie within bounds
 #[derive(Debug, Clone)]
-struct EvaluationWeights {
-    trust_differential: f64,
-    trust_absolute: f64,
-    round_progress: f64,
-    momentum: f64,
-    position_advantage: f64,
+pub struct GameStateRange {
+    /// Everything but trust; its trust values are ignored
+    pub state: GameState,
+    /// Inclusive `(low, high)` bounds on player 1's trust
+    pub player1_trust: (i32, i32),
+    /// Inclusive `(low, high)` bounds on player 2's trust
+    pub player2_trust: (i32, i32),
+}
+
+/// How much each term counts towards an evaluation. Build a custom set
+/// with `EvaluationWeights::builder()` and pass it to
+/// `Evaluator::with_weights`. Terms missing from serialized weights take
+/// their default weight.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EvaluationWeights {
+    pub trust_differential: f64,
+    pub trust_absolute: f64,
+    pub round_progress: f64,
+    pub momentum: f64,
+    pub position_advantage: f64,
+    pub claim_risk: f64,
+    pub over_acceptance: f64,
+    pub tempo: f64,
+    pub claim_pressure: f64,
+}
+
+/// Weighted contribution of each term to an evaluation, from
+/// `Evaluator::evaluate_explained`. Contributions are already scaled by the
+/// evaluator's competitiveness, so they add up to `unclamped_total`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvaluationBreakdown {
+    pub trust_differential: f64,
+    pub trust_absolute: f64,
+    pub round_progress: f64,
+    pub momentum: f64,
+    pub position_advantage: f64,
+    pub claim_risk: f64,
+    pub over_acceptance: f64,
+    pub tempo: f64,
+    pub claim_pressure: f64,
+    /// Reward for combined trust, weighted by how cooperative the evaluator is
+    pub cooperative: f64,
+    /// Sum of the contributions
+    pub unclamped_total: f64,
+    /// The evaluation itself: the sum clamped to [-100, 100], with any
+    /// tie-break jitter applied. In integer mode this is the fixed-point
+    /// score, while the contributions stay unrounded.
+    pub total: f64,
+}
+
+impl EvaluationBreakdown {
+    /// Every contribution, in field order
+    pub fn contributions(&self) -> [f64; 10] {
+        [
+            self.trust_differential,
+            self.trust_absolute,
+            self.round_progress,
+            self.momentum,
+            self.position_advantage,
+            self.claim_risk,
+            self.over_acceptance,
+            self.tempo,
+            self.claim_pressure,
+            self.cooperative,
+        ]
+    }
+}
+
+/// Fluent construction of `EvaluationWeights`, starting from the defaults.
+///
+/// ```
+/// use strategic_mind_optimizer::{EvaluationWeights, Evaluator, GameState, Phase, Player};
+///
+/// let weights = EvaluationWeights::builder()
+///     .momentum(5.0)
+///     .trust_differential(0.2)
+///     .build();
+/// let evaluator = Evaluator::with_weights(weights);
+///
+/// let state = GameState {
+///     round: 4,
+///     phase: Phase::Claim,
+///     player1_trust: 60,
+///     player2_trust: 50,
+///     current_claim: None,
+///     move_history: Vec::new(),
+/// };
+/// let eval = evaluator.evaluate(&state, Player::Player1);
+/// assert!((-100.0..=100.0).contains(&eval));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EvaluationWeightsBuilder {
+    weights: EvaluationWeights,
+}
+
+/// What to do with fields that name no evaluation term when reading
+/// weights from JSON
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnknownFields {
+    /// Skip them, so files written for newer or older versions still load
+    #[default]
+    Ignore,
+    /// Fail, to catch misspelled term names
+    Reject,
+}
+
+/// Weights that apply from `first_round` through `last_round` inclusive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledWeights {
+    pub first_round: u8,
+    pub last_round: u8,
+    pub weights: EvaluationWeights,
+}
+
+/// Evaluation weights that change over the course of a game, looked up by
+/// round. The first entry covering a round wins.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WeightSchedule {
+    pub entries: Vec<ScheduledWeights>,
+}
+
+impl WeightSchedule {
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Weights scheduled for `round`, if any entry covers it
+    pub fn weights_for(&self, round: u8) -> Option<&EvaluationWeights> {
+        self.entries
+            .iter()
+            .find(|entry| (entry.first_round..=entry.last_round).contains(&round))
+            .map(|entry| &entry.weights)
+    }
 }
 
 impl Default for EvaluationWeights {
@@ -22,43 +244,404 @@ impl Default for EvaluationWeights {
             round_progress: 0.3,
             momentum: 0.7,
             position_advantage: 0.8,
+            claim_risk: 0.5,
+            over_acceptance: 0.0,
+            tempo: 0.0,
+            claim_pressure: 0.0,
         }
     }
 }
 
+impl EvaluationWeightsBuilder {
+    pub fn trust_differential(mut self, weight: f64) -> Self {
+        self.weights.trust_differential = weight;
+        self
+    }
+
+    pub fn trust_absolute(mut self, weight: f64) -> Self {
+        self.weights.trust_absolute = weight;
+        self
+    }
+
+    pub fn round_progress(mut self, weight: f64) -> Self {
+        self.weights.round_progress = weight;
+        self
+    }
+
+    pub fn momentum(mut self, weight: f64) -> Self {
+        self.weights.momentum = weight;
+        self
+    }
+
+    pub fn position_advantage(mut self, weight: f64) -> Self {
+        self.weights.position_advantage = weight;
+        self
+    }
+
+    pub fn claim_risk(mut self, weight: f64) -> Self {
+        self.weights.claim_risk = weight;
+        self
+    }
+
+    pub fn over_acceptance(mut self, weight: f64) -> Self {
+        self.weights.over_acceptance = weight;
+        self
+    }
+
+    pub fn tempo(mut self, weight: f64) -> Self {
+        self.weights.tempo = weight;
+        self
+    }
+
+    pub fn claim_pressure(mut self, weight: f64) -> Self {
+        self.weights.claim_pressure = weight;
+        self
+    }
+
+    pub fn build(self) -> EvaluationWeights {
+        self.weights
+    }
+}
+
+impl EvaluationWeights {
+    pub fn builder() -> EvaluationWeightsBuilder {
+        EvaluationWeightsBuilder::default()
+    }
+
+    /// Read weights from a JSON object keyed by term name
+    pub fn from_json(json: &str, unknown_fields: UnknownFields) -> serde_json::Result<Self> {
+        let value: serde_json::Value = serde_json::from_str(json)?;
+
+        if unknown_fields == UnknownFields::Reject {
+            let known = serde_json::to_value(Self::default())?;
+            if let (Some(fields), Some(known)) = (value.as_object(), known.as_object()) {
+                if let Some(field) = fields.keys().find(|field| !known.contains_key(*field)) {
+                    return Err(serde::de::Error::custom(format!("unknown evaluation term `{field}`")));
+                }
+            }
+        }
+
+        serde_json::from_value(value)
+    }
+
+    /// Weight of the penalty for having a claim exposed to challenge
+    pub fn with_claim_risk(mut self, weight: f64) -> Self {
+        self.claim_risk = weight;
+        self
+    }
+
+    /// Weight of the penalty for accepting bold claims far more often than
+    /// challenging them. 0 (the default) disables it.
+    pub fn with_over_acceptance(mut self, weight: f64) -> Self {
+        self.over_acceptance = weight;
+        self
+    }
+
+    /// Weight of the tempo term, which rewards running down the clock while
+    /// ahead and dragging the game out while behind. 0 (the default)
+    /// disables it.
+    pub fn with_tempo(mut self, weight: f64) -> Self {
+        self.tempo = weight;
+        self
+    }
+
+    /// Weight of the claim pressure term, which favours the player who gets
+    /// to answer a standing bluff over the player who made it. 0 (the
+    /// default) disables it.
+    pub fn with_claim_pressure(mut self, weight: f64) -> Self {
+        self.claim_pressure = weight;
+        self
+    }
+}
+
 impl Evaluator {
     pub fn new() -> Self {
+        Self::with_weights(EvaluationWeights::default())
+    }
+
+    pub fn with_weights(weights: EvaluationWeights) -> Self {
         Self {
-            weights: EvaluationWeights::default(),
+            weights,
+            competitiveness: 1.0,
+            integer_mode: false,
+            tie_break_jitter: false,
+            schedule: None,
+            rollouts: None,
+            cache: None,
         }
     }
 
-    pub fn with_weights(weights: EvaluationWeights) -> Self {
-        Self { weights }
+    /// Evaluator with weights read from JSON, e.g. from an external tuner.
+    /// Unknown fields are ignored; use `EvaluationWeights::from_json` with
+    /// `UnknownFields::Reject` to refuse them.
+    pub fn from_weights_json(json: &str) -> serde_json::Result<Self> {
+        EvaluationWeights::from_json(json, UnknownFields::Ignore).map(Self::with_weights)
+    }
+
+    /// Like `from_weights_json`, reading the JSON from a file
+    pub fn from_weights_file(path: impl AsRef<Path>) -> serde_json::Result<Self> {
+        let json = std::fs::read_to_string(path).map_err(serde_json::Error::io)?;
+        Self::from_weights_json(&json)
+    }
+
+    /// Take weights from `schedule` by the evaluated state's round, falling
+    /// back to this evaluator's own weights in rounds it does not cover
+    pub fn with_schedule(mut self, schedule: WeightSchedule) -> Self {
+        self.schedule = Some(schedule);
+        self
+    }
+
+    /// Score states by playing `count` random games from them to the end
+    /// under the default rules and averaging the final trust differential,
+    /// clamped to [-100, 100]. Slower than the weighted terms but free of
+    /// their biases. Moves and challenge outcomes are drawn from `seed` and
+    /// the state, so a state always gets the same score, and the two
+    /// players' scores are opposite. `evaluate_explained` still breaks down
+    /// the weighted terms. A `count` of 0 turns rollouts off.
+    pub fn with_rollouts(mut self, count: u32, seed: u64) -> Self {
+        self.rollouts = (count > 0).then_some(Rollouts { count, seed });
+        self
+    }
+
+    /// Remember up to `capacity` evaluations, dropping the least recently
+    /// used, so a state scored again in a sibling subtree is looked up
+    /// rather than recomputed. Entries are keyed by the state's key and the
+    /// player: the momentum and over-acceptance terms read the move history,
+    /// so the history-blind Zobrist hash would mix up states they score
+    /// differently. The cache sits behind a lock and is shared by clones of
+    /// the evaluator, so one evaluator may serve parallel searches. A
+    /// `capacity` of 0 turns caching off.
+    pub fn with_cache(mut self, capacity: usize) -> Self {
+        self.cache = (capacity > 0).then(|| Arc::new(Mutex::new(LruCache::new(capacity))));
+        self
+    }
+
+    /// Hits, misses and occupancy of the cache, if there is one
+    pub fn cache_stats(&self) -> Option<CacheStats> {
+        self.cache
+            .as_ref()
+            .map(|cache| cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).stats())
+    }
+
+    /// Weights in effect for `state`
+    fn weights_for(&self, state: &GameState) -> &EvaluationWeights {
+        self.schedule
+            .as_ref()
+            .and_then(|schedule| schedule.weights_for(state.round))
+            .unwrap_or(&self.weights)
+    }
+
+    /// Combine terms in fixed-point integer arithmetic instead of floats.
+    /// Each term and weight is rounded to thousandths before being combined,
+    /// so scores are exact multiples of 0.001 and identical on every
+    /// platform; searches comparing them never see ties broken by rounding.
+    pub fn integer_mode(mut self, enabled: bool) -> Self {
+        self.integer_mode = enabled;
+        self
+    }
+
+    pub fn is_integer_mode(&self) -> bool {
+        self.integer_mode
+    }
+
+    /// Nudge every nonzero evaluation by less than 1e-7, derived from the
+    /// state's key, so distinct states practically never score exactly the
+    /// same and move ordering does not hinge on accidental ties. The nudge
+    /// never changes the sign or the value rounded to hundredths; zero
+    /// evaluations are left alone. Has no effect in integer mode.
+    pub fn with_tie_break_jitter(mut self, enabled: bool) -> Self {
+        self.tie_break_jitter = enabled;
+        self
+    }
+
+    /// Set how competitive the evaluation is, clamped to `[0, 1]`.
+    /// At 1.0 only the player's own position counts; at 0.0 both players'
+    /// trust is rewarded equally, for cooperative variants.
+    pub fn with_competitiveness(mut self, competitiveness: f64) -> Self {
+        self.competitiveness = competitiveness.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn competitiveness(&self) -> f64 {
+        self.competitiveness
     }
 
     /// Evaluate game state from perspective of given player
     /// Returns value between -100 and +100
     pub fn evaluate(&self, state: &GameState, player: Player) -> f64 {
-        let mut score = 0.0;
+        let Some(cache) = &self.cache else {
+            return self.evaluate_uncached(state, player);
+        };
+
+        // The lock is not held while evaluating, so threads only wait on
+        // each other for lookups
+        let key = (state.state_key(), player);
+        let cached = cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).get(&key);
+        if let Some(eval) = cached {
+            return eval;
+        }
+
+        let eval = self.evaluate_uncached(state, player);
+        cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).insert(key, eval);
+        eval
+    }
+
+    fn evaluate_uncached(&self, state: &GameState, player: Player) -> f64 {
+        let eval = match self.rollouts {
+            Some(rollouts) => self.evaluate_rollouts(state, player, rollouts),
+            None => self.evaluate_explained(state, player).total,
+        };
+        sanitize(eval)
+    }
+
+    fn evaluate_rollouts(&self, state: &GameState, player: Player, rollouts: Rollouts) -> f64 {
+        let mut rng = StdRng::seed_from_u64(rollouts.seed ^ state.state_key());
+        let tree = GameTree::new(state.clone());
+
+        let total: f64 = (0..rollouts.count)
+            .map(|_| {
+                let end = play_randomly(&tree, state.clone(), &mut rng);
+                let differential = (end.player1_trust - end.player2_trust) as f64;
+                match player {
+                    Player::Player1 => differential,
+                    Player::Player2 => -differential,
+                }
+            })
+            .sum();
 
-        // Trust differential (most important)
-        score += self.evaluate_trust_differential(state, player) * self.weights.trust_differential;
+        (total / rollouts.count as f64).clamp(-100.0, 100.0)
+    }
 
-        // Absolute trust position
-        score += self.evaluate_trust_absolute(state, player) * self.weights.trust_absolute;
+    /// Evaluate like `evaluate`, reporting how much each term contributed
+    pub fn evaluate_explained(&self, state: &GameState, player: Player) -> EvaluationBreakdown {
+        let [
+            trust_differential,
+            trust_absolute,
+            round_progress,
+            momentum,
+            position_advantage,
+            claim_risk,
+            over_acceptance,
+            tempo,
+            claim_pressure,
+        ] = self
+            .weighted_terms(state, player)
+            .map(|(term, weight)| self.competitiveness * term * weight);
+        let cooperative = (1.0 - self.competitiveness)
+            * self.evaluate_trust_total(state)
+            * self.weights_for(state).trust_differential;
 
-        // Round progress (endgame considerations)
-        score += self.evaluate_round_progress(state, player) * self.weights.round_progress;
+        let mut breakdown = EvaluationBreakdown {
+            trust_differential,
+            trust_absolute,
+            round_progress,
+            momentum,
+            position_advantage,
+            claim_risk,
+            over_acceptance,
+            tempo,
+            claim_pressure,
+            cooperative,
+            unclamped_total: 0.0,
+            total: 0.0,
+        };
+        breakdown.unclamped_total = breakdown.contributions().iter().sum();
 
-        // Momentum
-        score += self.evaluate_momentum(state, player) * self.weights.momentum;
+        breakdown.total = if self.integer_mode {
+            self.evaluate_fixed(state, player) as f64 / FIXED_POINT_SCALE as f64
+        } else {
+            // Clamp to reasonable range
+            let score = breakdown.unclamped_total.clamp(-100.0, 100.0);
 
-        // Position advantage
-        score += self.evaluate_position_advantage(state, player) * self.weights.position_advantage;
+            if self.tie_break_jitter {
+                jitter(score, state.state_key())
+            } else {
+                score
+            }
+        };
 
-        // Clamp to reasonable range
-        score.max(-100.0).min(100.0)
+        breakdown
+    }
+
+    /// Lowest and highest evaluation over the corners of `range`.
+    ///
+    /// Fully competitive evaluation rises with the player's own trust and
+    /// falls with the opponent's, so its extremes lie at the corners and the
+    /// interval covers every state in the range. With a lower
+    /// competitiveness the opponent's trust pulls both ways and the interval
+    /// is only an estimate.
+    pub fn evaluate_range(&self, range: &GameStateRange, player: Player) -> (f64, f64) {
+        let mut corner = range.state.clone();
+        let mut bounds = (f64::INFINITY, f64::NEG_INFINITY);
+
+        for p1 in [range.player1_trust.0, range.player1_trust.1] {
+            for p2 in [range.player2_trust.0, range.player2_trust.1] {
+                corner.player1_trust = p1;
+                corner.player2_trust = p2;
+                let eval = self.evaluate(&corner, player);
+                bounds = (bounds.0.min(eval), bounds.1.max(eval));
+            }
+        }
+
+        bounds
+    }
+
+    /// Evaluate in fixed-point thousandths, between -100_000 and +100_000
+    pub fn evaluate_fixed(&self, state: &GameState, player: Player) -> i64 {
+        let competitive: i64 = self
+            .weighted_terms(state, player)
+            .iter()
+            .map(|(term, weight)| to_fixed(*term) * to_fixed(*weight) / FIXED_POINT_SCALE)
+            .sum();
+        let cooperative = to_fixed(self.evaluate_trust_total(state))
+            * to_fixed(self.weights_for(state).trust_differential)
+            / FIXED_POINT_SCALE;
+
+        let competitiveness = to_fixed(self.competitiveness);
+        let score = (competitiveness * competitive + (FIXED_POINT_SCALE - competitiveness) * cooperative)
+            / FIXED_POINT_SCALE;
+
+        score.clamp(-100 * FIXED_POINT_SCALE, 100 * FIXED_POINT_SCALE)
+    }
+
+    /// Each competitive term paired with its weight
+    fn weighted_terms(&self, state: &GameState, player: Player) -> [(f64, f64); 9] {
+        let weights = self.weights_for(state);
+        let terms = [
+            // Trust differential (most important)
+            (self.evaluate_trust_differential(state, player), weights.trust_differential),
+            // Absolute trust position
+            (self.evaluate_trust_absolute(state, player), weights.trust_absolute),
+            // Round progress (endgame considerations)
+            (self.evaluate_round_progress(state, player), weights.round_progress),
+            // Momentum
+            (self.evaluate_momentum(state, player), weights.momentum),
+            // Position advantage
+            (self.evaluate_position_advantage(state, player), weights.position_advantage),
+            // Exposure of a pending claim to challenge
+            (self.evaluate_claim_risk(state, player), weights.claim_risk),
+            // Habitually accepting bold claims invites bluffs
+            (self.evaluate_over_acceptance(state, player), weights.over_acceptance),
+            // Whether the clock running down helps or hurts
+            (self.evaluate_tempo(state, player), weights.tempo),
+            // A standing bluff is there for the taking
+            (self.evaluate_claim_pressure(state, player), weights.claim_pressure),
+        ];
+
+        // Weights are the caller's to get wrong, but the terms are ours
+        debug_assert!(
+            terms.iter().all(|(term, _)| term.is_finite()),
+            "evaluation term is not finite: {:?}",
+            terms
+        );
+        terms
+    }
+
+    fn evaluate_trust_total(&self, state: &GameState) -> f64 {
+        // Combined trust above the 50/50 starting point, on the differential's scale
+        let surplus = state.player1_trust + state.player2_trust - 100;
+
+        (surplus as f64 / 3.0).clamp(-50.0, 50.0)
     }
 
     fn evaluate_trust_differential(&self, state: &GameState, player: Player) -> f64 {
@@ -70,7 +653,7 @@ impl Evaluator {
         let differential = my_trust - opp_trust;
 
         // Normalize to -50 to +50 range
-        (differential as f64 / 3.0).max(-50.0).min(50.0)
+        (differential as f64 / 3.0).clamp(-50.0, 50.0)
     }
 
     fn evaluate_trust_absolute(&self, state: &GameState, player: Player) -> f64 {
@@ -106,29 +689,108 @@ impl Evaluator {
         }
     }
 
+    /// How far the player's trust lead moved over the last few moves: the
+    /// lead now against the lead recorded at the oldest move in the window.
+    /// Moves without a trust snapshot are skipped, so a history that
+    /// recorded none has no momentum.
     fn evaluate_momentum(&self, state: &GameState, player: Player) -> f64 {
-        if state.move_history.len() < 3 {
+        let lead = |(player1_trust, player2_trust): (i32, i32)| match player {
+            Player::Player1 => player1_trust - player2_trust,
+            Player::Player2 => player2_trust - player1_trust,
+        };
+
+        let window = &state.move_history[state.move_history.len().saturating_sub(MOMENTUM_WINDOW + 1)..];
+        let Some(before) = window.iter().find_map(|m| m.trust_after) else {
+            return 0.0;
+        };
+
+        let change = lead((state.player1_trust, state.player2_trust)) - lead(before);
+        change as f64 / 3.0
+    }
+
+    fn evaluate_claim_risk(&self, state: &GameState, player: Player) -> f64 {
+        if state.phase != Phase::Challenge {
             return 0.0;
         }
 
-        let recent_moves = &state.move_history[state.move_history.len().saturating_sub(5)..];
+        let Some(claim) = &state.current_claim else {
+            return 0.0;
+        };
 
-        let my_moves: Vec<_> = recent_moves
-            .iter()
-            .filter(|m| {
-                (player == Player::Player1 && m.player == Player::Player1)
-                    || (player == Player::Player2 && m.player == Player::Player2)
+        if claimer(state) != Some(player) {
+            return 0.0;
+        }
+
+        // Bolder claims are challenged more successfully; bluffs doubly so
+        let mut risk = claim.boldness * 10.0;
+        if claim.is_bluff {
+            risk += 5.0;
+        }
+
+        -risk
+    }
+
+    fn evaluate_claim_pressure(&self, state: &GameState, player: Player) -> f64 {
+        if state.phase != Phase::Challenge {
+            return 0.0;
+        }
+
+        let Some(claim) = state.current_claim.as_ref().filter(|claim| claim.is_bluff) else {
+            return 0.0;
+        };
+        let Some(claimer) = claimer(state) else {
+            return 0.0;
+        };
+
+        // Bolder bluffs are easier to call and cost more when called
+        let pressure = claim.boldness * 20.0;
+        if claimer == player {
+            -pressure
+        } else {
+            pressure
+        }
+    }
+
+    fn evaluate_over_acceptance(&self, state: &GameState, player: Player) -> f64 {
+        // This player's responses to the opponent's bold claims
+        let responses: Vec<Action> = state
+            .move_history
+            .windows(2)
+            .filter(|pair| {
+                let bold = pair[0].claim.as_ref().is_some_and(|claim| claim.boldness >= BOLD_CLAIM);
+                pair[0].action == Action::MakeClaim && pair[0].player != player && bold && pair[1].player == player
             })
+            .map(|pair| pair[1].action)
             .collect();
 
-        if my_moves.is_empty() {
+        if responses.len() < MIN_BOLD_RESPONSES {
             return 0.0;
         }
 
-        // Calculate trend (simplified - in real impl would track trust changes)
-        let confidence_avg = my_moves.iter().map(|m| m.confidence).sum::<f64>() / my_moves.len() as f64;
+        let accepted = responses.iter().filter(|&&action| action == Action::Accept).count();
+        let accept_rate = accepted as f64 / responses.len() as f64;
+
+        // Accepting more than half of them is an open invitation to bluff
+        -(accept_rate - 0.5).max(0.0) * 40.0
+    }
+
+    fn evaluate_tempo(&self, state: &GameState, player: Player) -> f64 {
+        let (my_trust, opp_trust) = match player {
+            Player::Player1 => (state.player1_trust, state.player2_trust),
+            Player::Player2 => (state.player2_trust, state.player1_trust),
+        };
+
+        // Rounds played so far, counting how far into its cycle this round is
+        let cycle = match state.phase {
+            Phase::Claim => 0.0,
+            Phase::Challenge => 1.0 / 3.0,
+            Phase::Resolution => 2.0 / 3.0,
+        };
+        let elapsed = ((state.round as f64 + cycle) / 20.0).min(1.0);
 
-        (confidence_avg - 0.5) * 20.0
+        // A lead is worth more the less time is left to overturn it
+        let lead = ((my_trust - opp_trust) as f64 / 3.0).clamp(-50.0, 50.0);
+        lead * elapsed
     }
 
     fn evaluate_position_advantage(&self, state: &GameState, player: Player) -> f64 {
@@ -164,6 +826,171 @@ impl Evaluator {
     }
 }
 
+/// Whoever made the latest claim
+fn claimer(state: &GameState) -> Option<Player> {
+    state
+        .move_history
+        .iter()
+        .rev()
+        .find(|m| m.action == Action::MakeClaim)
+        .map(|m| m.player)
+}
+
+/// Play uniformly random moves from `state`, with challenge outcomes drawn
+/// by their probability, until the game ends or nobody can move
+fn play_randomly(tree: &GameTree, mut state: GameState, rng: &mut StdRng) -> GameState {
+    // Players alternate, and player 1 opens
+    let mut to_move = state.move_history.last().map_or(Player::Player1, |m| m.player.opponent());
+    let mut passed = false;
+
+    for _ in 0..MAX_ROLLOUT_PLIES {
+        if tree.is_terminal(&state) {
+            break;
+        }
+
+        let moves = tree.generate_moves(&state, to_move);
+        if moves.is_empty() {
+            if passed {
+                break;
+            }
+            passed = true;
+            to_move = to_move.opponent();
+            continue;
+        }
+        passed = false;
+
+        let chosen = &moves[rng.gen_range(0..moves.len())];
+        let mut outcomes = tree.resolution_outcomes(&state, chosen);
+        let roll: f64 = rng.gen();
+        let mut cumulative = 0.0;
+        let index = outcomes
+            .iter()
+            .position(|(_, probability)| {
+                cumulative += probability;
+                roll < cumulative
+            })
+            .unwrap_or(outcomes.len() - 1);
+
+        state = outcomes.swap_remove(index).0;
+        to_move = to_move.opponent();
+    }
+
+    state
+}
+
+/// Findings of `Evaluator::audit`
+#[cfg(feature = "testing")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvaluatorAudit {
+    /// Number of states sampled
+    pub samples: usize,
+    /// Whether eval(s, P1) ≈ -eval(s, P2) held for every sample
+    pub zero_sum: bool,
+    /// Largest |eval(s, P1) + eval(s, P2)| seen
+    pub max_zero_sum_error: f64,
+    /// Whether each player scores a state like the other player scores its mirror
+    pub symmetric: bool,
+    /// Whether raising a player's own trust never lowered their evaluation
+    pub monotonic_in_trust: bool,
+    /// Whether every evaluation stayed within [-100, 100]
+    pub bounded: bool,
+}
+
+#[cfg(feature = "testing")]
+impl Evaluator {
+    /// Sample a grid of states and their mirror images and report whether
+    /// this evaluator is zero-sum, symmetric, monotonic in trust and bounded.
+    /// An introspection tool for evaluator authors; it is slow by design.
+    pub fn audit(&self) -> EvaluatorAudit {
+        const TOLERANCE: f64 = 1e-9;
+        const TRUST_STEP: i32 = 10;
+
+        let mut audit = EvaluatorAudit {
+            samples: 0,
+            zero_sum: true,
+            max_zero_sum_error: 0.0,
+            symmetric: true,
+            monotonic_in_trust: true,
+            bounded: true,
+        };
+
+        for round in (1..=20).step_by(3) {
+            for p1_trust in (-40..100).step_by(TRUST_STEP as usize) {
+                for p2_trust in (-40..100).step_by(TRUST_STEP as usize) {
+                    let state = audit_state(round, p1_trust, p2_trust);
+                    let mirrored = audit_state(round, p2_trust, p1_trust);
+                    audit.samples += 1;
+
+                    let p1_eval = self.evaluate(&state, Player::Player1);
+                    let p2_eval = self.evaluate(&state, Player::Player2);
+
+                    let error = (p1_eval + p2_eval).abs();
+                    audit.max_zero_sum_error = audit.max_zero_sum_error.max(error);
+                    audit.zero_sum &= error <= TOLERANCE;
+                    audit.bounded &= p1_eval.abs() <= 100.0 && p2_eval.abs() <= 100.0;
+
+                    // Player 1 in the mirror is in player 2's shoes here
+                    let mirrored_eval = self.evaluate(&mirrored, Player::Player1);
+                    audit.symmetric &= (mirrored_eval - p2_eval).abs() <= TOLERANCE;
+
+                    let stronger = audit_state(round, p1_trust + TRUST_STEP, p2_trust);
+                    audit.monotonic_in_trust &=
+                        self.evaluate(&stronger, Player::Player1) >= p1_eval - TOLERANCE;
+                }
+            }
+        }
+
+        audit
+    }
+}
+
+#[cfg(feature = "testing")]
+fn audit_state(round: u8, player1_trust: i32, player2_trust: i32) -> GameState {
+    GameState {
+        round,
+        phase: crate::Phase::Claim,
+        player1_trust,
+        player2_trust,
+        current_claim: None,
+        move_history: Vec::new(),
+    }
+}
+
+/// Replace a NaN evaluation with 0 and saturate infinities at the ±100
+/// bounds, so a miscalibrated weight cannot derail the search
+fn sanitize(eval: f64) -> f64 {
+    if eval.is_nan() {
+        0.0
+    } else {
+        eval.clamp(-100.0, 100.0)
+    }
+}
+
+/// Move `score` by a tiny key-derived amount, toward zero unless that would
+/// change its value rounded to hundredths
+fn jitter(score: f64, key: u64) -> f64 {
+    if score == 0.0 {
+        return score;
+    }
+
+    // Top 53 bits of the key as a fraction in [0, 1)
+    let fraction = (key >> 11) as f64 / (1u64 << 53) as f64;
+    let offset = MAX_JITTER * fraction * score.signum();
+
+    let centi = |value: f64| (value * 100.0).round();
+    let toward_zero = score - offset;
+    if centi(toward_zero) == centi(score) {
+        toward_zero
+    } else {
+        score + offset
+    }
+}
+
+/// Round a value to fixed-point thousandths
+fn to_fixed(value: f64) -> i64 {
+    (value * FIXED_POINT_SCALE as f64).round() as i64
+}
+
 impl Default for Evaluator {
     fn default() -> Self {
         Self::new()
@@ -173,7 +1000,6 @@ impl Default for Evaluator {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Phase;
 
     fn create_test_state(p1_trust: i32, p2_trust: i32) -> GameState {
         GameState {
@@ -195,6 +1021,54 @@ mod tests {
         assert!(eval.abs() < 10.0); // Should be near zero for balanced state
     }
 
+    #[test]
+    fn test_rollouts_score_won_state() {
+        let mut state = create_test_state(85, 0);
+        state.round = 18;
+        let evaluator = Evaluator::new().with_rollouts(32, 5);
+
+        let eval = evaluator.evaluate(&state, Player::Player1);
+        assert!(eval > 0.0);
+        assert_eq!(evaluator.evaluate(&state, Player::Player2), -eval);
+        // Same seed and state, same playouts
+        assert_eq!(Evaluator::new().with_rollouts(32, 5).evaluate(&state, Player::Player1), eval);
+    }
+
+    #[test]
+    fn test_cache_hits_identical_state() {
+        let evaluator = Evaluator::new().with_cache(16);
+        let state = create_test_state(60, 40);
+
+        let first = evaluator.evaluate(&state, Player::Player1);
+        let second = evaluator.evaluate(&state.clone(), Player::Player1);
+        assert_eq!(first, second);
+
+        let stats = evaluator.cache_stats().unwrap();
+        assert_eq!((stats.hits, stats.misses), (1, 1));
+        assert_eq!(stats.hit_rate(), 0.5);
+
+        // The other player's view is a separate entry
+        evaluator.evaluate(&state, Player::Player2);
+        assert_eq!(evaluator.cache_stats().unwrap().misses, 2);
+        assert!(Evaluator::new().cache_stats().is_none());
+    }
+
+    #[test]
+    fn test_evaluate_range_brackets_midpoint() {
+        let evaluator = Evaluator::new();
+        let range = GameStateRange {
+            state: create_test_state(0, 0),
+            player1_trust: (40, 95),
+            player2_trust: (-45, 60),
+        };
+
+        let (low, high) = evaluator.evaluate_range(&range, Player::Player1);
+        let midpoint = evaluator.evaluate(&create_test_state(67, 7), Player::Player1);
+
+        assert!(low < high);
+        assert!(low <= midpoint && midpoint <= high, "{midpoint} outside [{low}, {high}]");
+    }
+
     #[test]
     fn test_evaluator_advantage() {
         let evaluator = Evaluator::new();
@@ -212,4 +1086,382 @@ mod tests {
         
         assert!(eval < 0.0); // Player1 should have negative evaluation
     }
+
+    #[test]
+    fn test_integer_mode_matches_float_mode() {
+        let float = Evaluator::new();
+        let fixed = Evaluator::new().integer_mode(true);
+
+        for round in [1, 10, 16, 19] {
+            for p1_trust in (-40..=95).step_by(15) {
+                for p2_trust in (-40..=95).step_by(20) {
+                    let mut state = create_test_state(p1_trust, p2_trust);
+                    state.round = round;
+
+                    for player in [Player::Player1, Player::Player2] {
+                        let a = float.evaluate(&state, player);
+                        let b = fixed.evaluate(&state, player);
+                        assert!((a - b).abs() < 0.01, "{a} vs {b} at {state:?}");
+                        assert_eq!(to_fixed(b), fixed.evaluate_fixed(&state, player));
+                    }
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_audit_default_evaluator() {
+        let audit = Evaluator::new().audit();
+
+        // Each player gets the absolute trust bonus independently, so the
+        // default evaluator is not zero-sum
+        assert!(!audit.zero_sum);
+        assert!(audit.max_zero_sum_error > 0.0);
+        assert!(audit.symmetric);
+        assert!(audit.monotonic_in_trust);
+        assert!(audit.bounded);
+    }
+
+    #[test]
+    fn test_non_finite_weights_give_bounded_evaluations() {
+        // The absolute term is 0 below 80 trust, and 0 * inf is NaN
+        let weights = EvaluationWeights::builder().trust_absolute(f64::INFINITY).build();
+        let infinite = Evaluator::with_weights(weights);
+        assert_eq!(infinite.evaluate(&create_test_state(80, 50), Player::Player1), 100.0);
+        assert_eq!(infinite.evaluate(&create_test_state(80, 50), Player::Player2), 0.0);
+
+        let nan = Evaluator::with_weights(EvaluationWeights::builder().momentum(f64::NAN).build());
+        assert_eq!(nan.evaluate(&create_test_state(60, 50), Player::Player1), 0.0);
+    }
+
+    #[test]
+    fn test_claim_risk_penalizes_bold_bluffs() {
+        let claim_state = |boldness: f64, is_bluff: bool| {
+            let claim = crate::Claim {
+                description: String::new(),
+                claim_type: crate::ClaimType::Accusation,
+                boldness,
+                is_bluff,
+            };
+            let mut state = create_test_state(50, 50);
+            state.phase = Phase::Challenge;
+            state.current_claim = Some(claim.clone());
+            state.move_history.push(crate::Move {
+                action: Action::MakeClaim,
+                player: Player::Player1,
+                claim: Some(claim),
+                confidence: 0.8,
+                trust_after: None,
+            });
+            state
+        };
+
+        let evaluator = Evaluator::new();
+        let bold_bluff = evaluator.evaluate(&claim_state(0.8, true), Player::Player1);
+        let timid_honest = evaluator.evaluate(&claim_state(0.2, false), Player::Player1);
+        assert!(bold_bluff < timid_honest);
+
+        // Only the claimer is exposed
+        assert_eq!(
+            evaluator.evaluate(&claim_state(0.8, true), Player::Player2),
+            evaluator.evaluate(&claim_state(0.2, false), Player::Player2)
+        );
+    }
+
+    #[test]
+    fn test_claim_pressure_favours_answering_a_bluff() {
+        let claim_state = |is_bluff: bool| {
+            let claim = crate::Claim {
+                description: String::new(),
+                claim_type: crate::ClaimType::Prediction,
+                boldness: 0.8,
+                is_bluff,
+            };
+            let mut state = create_test_state(50, 50);
+            state.phase = Phase::Challenge;
+            state.current_claim = Some(claim.clone());
+            state.move_history.push(crate::Move {
+                action: Action::MakeClaim,
+                player: Player::Player1,
+                claim: Some(claim),
+                confidence: 0.76,
+                trust_after: None,
+            });
+            state
+        };
+
+        let weights = EvaluationWeights::default().with_claim_risk(0.0).with_claim_pressure(1.0);
+        let evaluator = Evaluator::with_weights(weights);
+
+        // A standing bluff helps the player about to answer it and hurts its maker
+        let bluff = claim_state(true);
+        assert_eq!(evaluator.evaluate(&bluff, Player::Player2), 16.0);
+        assert_eq!(evaluator.evaluate(&bluff, Player::Player1), -16.0);
+
+        // An honest claim, or the same bluff without the term, changes nothing
+        let honest = claim_state(false);
+        assert_eq!(evaluator.evaluate(&honest, Player::Player2), 0.0);
+        let without = Evaluator::with_weights(EvaluationWeights::default().with_claim_risk(0.0));
+        assert_eq!(without.evaluate(&bluff, Player::Player2), 0.0);
+    }
+
+    #[test]
+    fn test_momentum_follows_trust_changes() {
+        // Player 2 claims and player 1 challenges, twice, with the
+        // challenges all coming out the same way
+        let history = |outcome: usize| {
+            let mut state = create_test_state(50, 50);
+            let tree = crate::GameTree::new(state.clone());
+            for _ in 0..2 {
+                let claim = crate::Move {
+                    action: Action::MakeClaim,
+                    player: Player::Player2,
+                    claim: Some(crate::Claim {
+                        description: String::new(),
+                        claim_type: crate::ClaimType::Prediction,
+                        boldness: 0.6,
+                        is_bluff: outcome == 1,
+                    }),
+                    confidence: 0.5,
+                    trust_after: None,
+                };
+                let challenge = crate::Move {
+                    action: Action::Challenge,
+                    player: Player::Player1,
+                    claim: None,
+                    confidence: 0.5,
+                    trust_after: None,
+                };
+                tree.make_outcome(&mut state, &claim, 0);
+                tree.make_outcome(&mut state, &challenge, outcome);
+            }
+            state
+        };
+
+        let evaluator = Evaluator::new();
+        let rising = history(1);
+        assert_eq!(rising.move_history.last().unwrap().trust_after, Some((80, 50)));
+        assert!(evaluator.evaluate_momentum(&rising, Player::Player1) > 0.0);
+        assert!(evaluator.evaluate_momentum(&rising, Player::Player2) < 0.0);
+
+        let falling = history(0);
+        assert!(evaluator.evaluate_momentum(&falling, Player::Player1) < 0.0);
+
+        // Without trust snapshots there is nothing to go on
+        let mut unrecorded = rising.clone();
+        unrecorded.move_history.iter_mut().for_each(|m| m.trust_after = None);
+        assert_eq!(evaluator.evaluate_momentum(&unrecorded, Player::Player1), 0.0);
+    }
+
+    #[test]
+    fn test_over_acceptance_penalizes_accepting_bold_claims() {
+        let history = |responses: &[Action]| {
+            let mut state = create_test_state(50, 50);
+            for &response in responses {
+                state.move_history.push(crate::Move {
+                    action: Action::MakeClaim,
+                    player: Player::Player2,
+                    claim: Some(crate::Claim {
+                        description: String::new(),
+                        claim_type: crate::ClaimType::Prediction,
+                        boldness: 0.8,
+                        is_bluff: true,
+                    }),
+                    confidence: 0.5,
+                    trust_after: None,
+                });
+                state.move_history.push(crate::Move {
+                    action: response,
+                    player: Player::Player1,
+                    claim: None,
+                    confidence: 0.6,
+                    trust_after: None,
+                });
+            }
+            state
+        };
+
+        let evaluator = Evaluator::with_weights(EvaluationWeights::default().with_over_acceptance(1.0));
+        let pushover = history(&[Action::Accept; 6]);
+        let balanced = history(&[Action::Accept, Action::Challenge, Action::Accept, Action::Challenge]);
+
+        assert!(
+            evaluator.evaluate(&pushover, Player::Player1) < evaluator.evaluate(&balanced, Player::Player1)
+        );
+
+        // Disabled by default
+        let plain = Evaluator::new();
+        assert_eq!(
+            plain.evaluate(&pushover, Player::Player1),
+            plain.evaluate(&balanced, Player::Player1)
+        );
+    }
+
+    #[test]
+    fn test_tempo_favors_ending_rounds_when_ahead() {
+        let evaluator = Evaluator::with_weights(EvaluationWeights::default().with_tempo(1.0));
+        let gain_from_resolving = |p1_trust: i32, p2_trust: i32| {
+            let mut challenged = create_test_state(p1_trust, p2_trust);
+            challenged.phase = Phase::Challenge;
+            let mut resolved = challenged.clone();
+            resolved.phase = Phase::Resolution;
+
+            evaluator.evaluate(&resolved, Player::Player1) - evaluator.evaluate(&challenged, Player::Player1)
+        };
+
+        let ahead = gain_from_resolving(70, 40);
+        let behind = gain_from_resolving(40, 70);
+        assert!(ahead > 0.0);
+        assert!(behind < 0.0);
+
+        // Disabled by default
+        let mut resolved = create_test_state(70, 40);
+        resolved.phase = Phase::Resolution;
+        assert_eq!(
+            Evaluator::new().evaluate(&resolved, Player::Player1),
+            Evaluator::new().evaluate(&create_test_state(70, 40), Player::Player1)
+        );
+    }
+
+    #[test]
+    fn test_evaluate_explained_sums_to_total() {
+        let mut state = create_test_state(95, 10);
+        state.round = 20;
+        let evaluator = Evaluator::new().with_competitiveness(0.9);
+
+        let breakdown = evaluator.evaluate_explained(&state, Player::Player1);
+        let sum: f64 = breakdown.contributions().iter().sum();
+        assert!((sum - breakdown.unclamped_total).abs() < 1e-9);
+        assert!(breakdown.trust_differential > 0.0);
+        assert!(breakdown.cooperative > 0.0);
+
+        // Far enough ahead that the total is clamped
+        assert!(breakdown.unclamped_total > 100.0);
+        assert_eq!(breakdown.total, 100.0);
+        assert_eq!(evaluator.evaluate(&state, Player::Player1), breakdown.total);
+    }
+
+    #[test]
+    fn test_weights_builder_overrides_named_terms() {
+        let weights = EvaluationWeights::builder().momentum(4.0).tempo(1.5).build();
+        let defaults = EvaluationWeights::default();
+
+        assert_eq!(weights.momentum, 4.0);
+        assert_eq!(weights.tempo, 1.5);
+        assert_eq!(weights.trust_differential, defaults.trust_differential);
+        assert_eq!(weights.claim_risk, defaults.claim_risk);
+    }
+
+    #[test]
+    fn test_weights_json_round_trip() {
+        let weights = EvaluationWeights::builder().trust_differential(2.0).build();
+        let json = serde_json::to_string(&weights).unwrap();
+
+        let state = create_test_state(70, 40);
+        let loaded = Evaluator::from_weights_json(&json).unwrap();
+        assert_eq!(
+            loaded.evaluate(&state, Player::Player1),
+            Evaluator::with_weights(weights).evaluate(&state, Player::Player1)
+        );
+        assert!(loaded.evaluate(&state, Player::Player1) > Evaluator::new().evaluate(&state, Player::Player1));
+
+        // Missing terms keep their defaults; unknown ones are only an error on request
+        let partial = r#"{"trust_differential": 2.0, "bluff_bonus": 1.0}"#;
+        let lenient = Evaluator::from_weights_json(partial).unwrap();
+        assert_eq!(lenient.evaluate(&state, Player::Player1), loaded.evaluate(&state, Player::Player1));
+        assert!(EvaluationWeights::from_json(partial, UnknownFields::Reject).is_err());
+        assert!(EvaluationWeights::from_json(&json, UnknownFields::Reject).is_ok());
+
+        let path = std::env::temp_dir().join(format!("weights-{}.json", std::process::id()));
+        std::fs::write(&path, &json).unwrap();
+        let from_file = Evaluator::from_weights_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(from_file.evaluate(&state, Player::Player1), loaded.evaluate(&state, Player::Player1));
+        assert!(Evaluator::from_weights_file(&path).is_err());
+    }
+
+    #[test]
+    fn test_weight_schedule_switches_at_round_boundary() {
+        let schedule = WeightSchedule::from_json(
+            r#"{"entries": [
+                {"first_round": 1, "last_round": 10, "weights": {
+                    "trust_differential": 0.2, "trust_absolute": 0.0, "round_progress": 0.0,
+                    "momentum": 0.0, "position_advantage": 1.0, "claim_risk": 0.0,
+                    "over_acceptance": 0.0, "tempo": 0.0}},
+                {"first_round": 11, "last_round": 20, "weights": {
+                    "trust_differential": 1.5, "trust_absolute": 0.0, "round_progress": 0.0,
+                    "momentum": 0.0, "position_advantage": 0.2, "claim_risk": 0.0,
+                    "over_acceptance": 0.0, "tempo": 0.0}}
+            ]}"#,
+        )
+        .unwrap();
+        let evaluator = Evaluator::new().with_schedule(schedule);
+
+        let mut early = create_test_state(70, 40);
+        early.round = 10;
+        let mut late = early.clone();
+        late.round = 11;
+
+        assert!((evaluator.evaluate(&early, Player::Player1) - 2.0).abs() < 1e-9);
+        assert!((evaluator.evaluate(&late, Player::Player1) - 15.0).abs() < 1e-9);
+
+        // Rounds the schedule does not cover use the evaluator's own weights
+        let mut uncovered = early.clone();
+        uncovered.round = 25;
+        assert_eq!(
+            evaluator.evaluate(&uncovered, Player::Player1),
+            Evaluator::new().evaluate(&uncovered, Player::Player1)
+        );
+    }
+
+    #[test]
+    fn test_tie_break_jitter() {
+        let plain = Evaluator::new();
+        let jittered = Evaluator::new().with_tie_break_jitter(true);
+
+        for p1_trust in (-40..=95).step_by(5) {
+            for p2_trust in (-40..=95).step_by(15) {
+                let state = create_test_state(p1_trust, p2_trust);
+                let a = plain.evaluate(&state, Player::Player1);
+                let b = jittered.evaluate(&state, Player::Player1);
+
+                assert_eq!(a.signum(), b.signum());
+                assert_eq!((a * 100.0).round(), (b * 100.0).round());
+                assert!((a - b).abs() <= MAX_JITTER);
+            }
+        }
+
+        // A claim left over in the claim phase changes the key but not the score
+        let first = create_test_state(80, 30);
+        let mut second = create_test_state(80, 30);
+        second.current_claim = Some(crate::Claim {
+            description: String::new(),
+            claim_type: crate::ClaimType::Alliance,
+            boldness: 0.4,
+            is_bluff: false,
+        });
+        assert_eq!(plain.evaluate(&first, Player::Player1), plain.evaluate(&second, Player::Player1));
+        assert_ne!(
+            jittered.evaluate(&first, Player::Player1),
+            jittered.evaluate(&second, Player::Player1)
+        );
+    }
+
+    #[test]
+    fn test_competitiveness_blend() {
+        let cooperative = Evaluator::new().with_competitiveness(0.0);
+        let leading = create_test_state(95, 85);
+        let trailing = create_test_state(85, 95);
+
+        // Only total trust matters, so both sides of either state score the same
+        let eval = cooperative.evaluate(&leading, Player::Player1);
+        assert!(eval > 20.0);
+        assert_eq!(eval, cooperative.evaluate(&trailing, Player::Player1));
+        assert_eq!(eval, cooperative.evaluate(&leading, Player::Player2));
+
+        let competitive = Evaluator::new().with_competitiveness(1.0);
+        assert!(competitive.evaluate(&create_test_state(70, 30), Player::Player1) > 0.0);
+        assert!(competitive.evaluate(&create_test_state(30, 70), Player::Player1) < 0.0);
+    }
 }
\ No newline at end of file
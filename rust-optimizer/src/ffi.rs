@@ -1,11 +1,37 @@
 //! Foreign Function Interface for Java/Python integration
-//! Provides both C-style FFI and JNI bindings
+//! Provides both C-style FFI and JNI bindings, plus browser bindings on
+//! wasm32. There the C entry points are not exported under their own
+//! names, which belong to the browser bindings.
 
-use crate::{AlphaBetaSearch, GameState, Player};
+use crate::evaluation::{EvaluationWeights, Evaluator, StateEvaluator, UnknownFields};
+use crate::instrument;
+use crate::{AlphaBetaSearch, ClaimType, GameState, GameTree, MinimaxSearch, Move, Player, RiskProfile};
 use std::ffi::{CStr, CString};
 use std::os::raw::c_char;
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
 use serde::{Serialize, Deserialize};
 
+/// Default cap on claim description length, in characters
+pub const DEFAULT_MAX_DESCRIPTION_LEN: usize = 256;
+
+pub(crate) static MAX_DESCRIPTION_LEN: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_DESCRIPTION_LEN);
+
+/// Moves scoring below this on `Move::consistency_score` are reported
+const CONSISTENCY_WARNING_THRESHOLD: f64 = 0.5;
+
+static WARN_INCONSISTENT_MOVES: AtomicBool = AtomicBool::new(false);
+
+/// Dedicated pool for batch operations, kept apart from the global rayon
+/// pool that interactive searches use
+static BATCH_POOL: OnceLock<rayon::ThreadPool> = OnceLock::new();
+
+thread_local! {
+    /// Error recorded by the latest failing call on this thread
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
 /// Result of a search operation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchResult {
@@ -14,63 +40,269 @@ pub struct SearchResult {
     pub nodes_explored: u64,
     pub depth_reached: u8,
     pub time_ms: u64,
+    /// Why the weights passed to `search_with_weights` were rejected in
+    /// favour of the defaults; absent when they were used
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weights_error: Option<String>,
 }
 
+/// The chosen move. The claim fields describe the claim of a `MakeClaim`
+/// move and are left out of the JSON for challenges and accepts, so
+/// callers that predate them parse results unchanged.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MoveResult {
     pub action: String,
     pub confidence: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claim_type: Option<ClaimType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub boldness: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_bluff: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl From<&Move> for MoveResult {
+    fn from(m: &Move) -> Self {
+        Self {
+            action: format!("{:?}", m.action),
+            confidence: m.confidence,
+            claim_type: m.claim.as_ref().map(|claim| claim.claim_type),
+            boldness: m.claim.as_ref().map(|claim| claim.boldness),
+            is_bluff: m.claim.as_ref().map(|claim| claim.is_bluff),
+            description: m.claim.as_ref().map(|claim| claim.description.clone()),
+        }
+    }
+}
+
+impl From<crate::SearchResult> for SearchResult {
+    fn from(result: crate::SearchResult) -> Self {
+        Self {
+            best_move: Some(MoveResult::from(&result.best_move)),
+            evaluation: result.evaluation,
+            nodes_explored: result.nodes_explored,
+            depth_reached: result.depth_reached,
+            time_ms: result.time_ms,
+            weights_error: None,
+        }
+    }
 }
 
 /// Search for optimal move using alpha-beta pruning (C-style FFI)
 /// 
 /// # Safety
 /// This function is unsafe because it deals with raw pointers from FFI
-#[no_mangle]
+#[cfg_attr(not(target_arch = "wasm32"), no_mangle)]
 pub unsafe extern "C" fn search_optimal_move(
     game_state_json: *const c_char,
     max_depth: u8,
     player_id: u8,
 ) -> *mut c_char {
+    run_search(game_state_json, player_id, SearchOptions::new(max_depth))
+}
+
+/// Same as `search_optimal_move`, but returns pretty-printed JSON.
+/// Intended for inspecting results by hand in bridge logs.
+/// 
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from FFI
+#[cfg_attr(not(target_arch = "wasm32"), no_mangle)]
+pub unsafe extern "C" fn search_optimal_move_pretty(
+    game_state_json: *const c_char,
+    max_depth: u8,
+    player_id: u8,
+) -> *mut c_char {
+    let options = SearchOptions {
+        pretty: true,
+        ..SearchOptions::new(max_depth)
+    };
+    run_search(game_state_json, player_id, options)
+}
+
+/// Same as `search_optimal_move`, but challenges are resolved from `seed`
+/// so the same state and seed always produce the same decision.
+/// A seed of 0 keeps the default nondeterministic behavior.
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from FFI
+#[cfg_attr(not(target_arch = "wasm32"), no_mangle)]
+pub unsafe extern "C" fn search_optimal_move_seeded(
+    game_state_json: *const c_char,
+    max_depth: u8,
+    player_id: u8,
+    seed: u64,
+) -> *mut c_char {
+    let options = SearchOptions {
+        seed: if seed == 0 { None } else { Some(seed) },
+        ..SearchOptions::new(max_depth)
+    };
+    run_search(game_state_json, player_id, options)
+}
+
+/// Same as `search_optimal_move`, but searched with plain minimax instead
+/// of alpha-beta. Slower, since nothing is pruned; meant for comparing
+/// against the reference algorithm and for teaching.
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from FFI
+#[cfg_attr(not(target_arch = "wasm32"), no_mangle)]
+pub unsafe extern "C" fn search_optimal_move_minimax(
+    game_state_json: *const c_char,
+    max_depth: u8,
+    player_id: u8,
+) -> *mut c_char {
+    let options = SearchOptions {
+        engine: Engine::Minimax,
+        ..SearchOptions::new(max_depth)
+    };
+    run_search(game_state_json, player_id, options)
+}
+
+/// Same as `search_optimal_move`, but the engine plays with a risk
+/// profile: 0 cautious, 1 balanced (what `search_optimal_move` uses) or
+/// 2 aggressive. Any other profile is rejected with a null result.
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from FFI
+#[cfg_attr(not(target_arch = "wasm32"), no_mangle)]
+pub unsafe extern "C" fn search_with_risk_profile(
+    game_state_json: *const c_char,
+    max_depth: u8,
+    player_id: u8,
+    risk_profile: u8,
+) -> *mut c_char {
+    let Some(risk_profile) = RiskProfile::from_id(risk_profile) else {
+        clear_last_error();
+        record_error("invalid_argument", format!("invalid risk_profile {}", risk_profile));
+        return std::ptr::null_mut();
+    };
+    let options = SearchOptions {
+        risk_profile,
+        ..SearchOptions::new(max_depth)
+    };
+    run_search(game_state_json, player_id, options)
+}
+
+/// Same as `search_optimal_move`, but leaves are scored with the
+/// `EvaluationWeights` in `weights_json`, e.g. to compare weight sets
+/// without rebuilding the library. Terms it leaves out keep their default
+/// weights. If the weights are null, malformed or name an unknown term,
+/// the search runs with the default weights and the result's
+/// `weights_error` says why.
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers from FFI
+#[cfg_attr(not(target_arch = "wasm32"), no_mangle)]
+pub unsafe extern "C" fn search_with_weights(
+    game_state_json: *const c_char,
+    weights_json: *const c_char,
+    max_depth: u8,
+    player_id: u8,
+) -> *mut c_char {
+    let (weights, weights_error) = match parse_weights(weights_json) {
+        Ok(weights) => (weights, None),
+        Err(e) => {
+            instrument::ffi_warning("FFI", format!("using default weights: {}", e));
+            (EvaluationWeights::default(), Some(e))
+        }
+    };
+    let options = SearchOptions {
+        evaluator: Arc::new(Evaluator::with_weights(weights)),
+        weights_error,
+        ..SearchOptions::new(max_depth)
+    };
+    run_search(game_state_json, player_id, options)
+}
+
+/// Search algorithm behind an FFI search
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Engine {
+    AlphaBeta,
+    Minimax,
+}
+
+/// How an FFI search is run and reported
+struct SearchOptions {
+    max_depth: u8,
+    seed: Option<u64>,
+    pretty: bool,
+    engine: Engine,
+    evaluator: Arc<dyn StateEvaluator>,
+    weights_error: Option<String>,
+    /// Only used by alpha-beta
+    risk_profile: RiskProfile,
+}
+
+impl SearchOptions {
+    fn new(max_depth: u8) -> Self {
+        Self {
+            max_depth,
+            seed: None,
+            pretty: false,
+            engine: Engine::AlphaBeta,
+            evaluator: Arc::new(Evaluator::new()),
+            weights_error: None,
+            risk_profile: RiskProfile::Balanced,
+        }
+    }
+}
+
+unsafe fn run_search(game_state_json: *const c_char, player_id: u8, options: SearchOptions) -> *mut c_char {
+    clear_last_error();
+    guard_panics("search", std::ptr::null_mut(), || search_json(game_state_json, player_id, options))
+}
+
+unsafe fn search_json(game_state_json: *const c_char, player_id: u8, options: SearchOptions) -> *mut c_char {
     // Safety check
     if game_state_json.is_null() {
-        eprintln!("[FFI] Error: Null game_state_json pointer");
+        record_error("null_pointer", "game_state_json is null");
         return std::ptr::null_mut();
     }
 
-    // Convert C string to Rust string
-    let c_str = match CStr::from_ptr(game_state_json).to_str() {
-        Ok(s) => s,
-        Err(e) => {
-            eprintln!("[FFI] Error converting C string: {}", e);
+    let state = match parse_state(game_state_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+
+    let player = match Player::from_id(player_id) {
+        Some(p) => p,
+        None => {
+            record_error("invalid_argument", format!("invalid player_id {}", player_id));
             return std::ptr::null_mut();
         }
     };
 
-    // Parse JSON
-    let state: GameState = match serde_json::from_str(c_str) {
-        Ok(s) => s,
-        Err(e) => {
-            eprintln!("[FFI] JSON parse error: {}", e);
-            return std::ptr::null_mut();
+    // Perform search
+    let evaluator = options.evaluator;
+    let result = match options.engine {
+        Engine::AlphaBeta => {
+            let mut search = AlphaBetaSearch::new(options.max_depth, true)
+                .with_evaluator(evaluator)
+                .with_risk_profile(options.risk_profile);
+            if let Some(seed) = options.seed {
+                search = search.with_seed(seed);
+            }
+            search.search(&state, player)
         }
+        Engine::Minimax => MinimaxSearch::new(options.max_depth)
+            .with_evaluator(evaluator)
+            .search(&state, player),
     };
+    let mut result = SearchResult::from(result);
+    result.weights_error = options.weights_error;
 
-    let player = if player_id == 1 {
-        Player::Player1
+    // Serialize result
+    let serialized = if options.pretty {
+        serde_json::to_string_pretty(&result)
     } else {
-        Player::Player2
+        serde_json::to_string(&result)
     };
 
-    // Perform search
-    let mut search = AlphaBetaSearch::new(max_depth, true);
-    let result: SearchResult = search.search(&state, player);
-
-    // Serialize result
-    let result_json = match serde_json::to_string(&result) {
+    let result_json = match serialized {
         Ok(json) => json,
         Err(e) => {
-            eprintln!("[FFI] JSON serialization error: {}", e);
+            record_error("serialization", format!("JSON serialization error: {}", e));
             return std::ptr::null_mut();
         }
     };
@@ -79,18 +311,121 @@ pub unsafe extern "C" fn search_optimal_move(
     match CString::new(result_json) {
         Ok(c_string) => c_string.into_raw(),
         Err(e) => {
-            eprintln!("[FFI] CString creation error: {}", e);
+            record_error("serialization", format!("CString creation error: {}", e));
             std::ptr::null_mut()
         }
     }
 }
 
+/// Parse a game state from a C string, logging any error.
+/// Oversized claim descriptions are truncated so that they are not
+/// cloned into every node of the search.
+///
+/// # Safety
+/// `game_state_json` must be a valid, NUL-terminated C string
+unsafe fn parse_state(game_state_json: *const c_char) -> Option<GameState> {
+    // Convert C string to Rust string
+    let c_str = match CStr::from_ptr(game_state_json).to_str() {
+        Ok(s) => s,
+        Err(e) => {
+            record_error("invalid_input", format!("input is not valid UTF-8: {}", e));
+            return None;
+        }
+    };
+
+    // Parse JSON
+    let mut state: GameState = match serde_json::from_str(c_str) {
+        Ok(s) => s,
+        Err(e) => {
+            record_error("parse", format!("JSON parse error: {}", e));
+            return None;
+        }
+    };
+
+    if let Err(e) = state.validate() {
+        record_error("invalid_state", e);
+        return None;
+    }
+
+    truncate_descriptions(&mut state, MAX_DESCRIPTION_LEN.load(Ordering::Relaxed));
+    if WARN_INCONSISTENT_MOVES.load(Ordering::Relaxed) {
+        warn_inconsistent_moves(&state);
+    }
+    Some(state)
+}
+
+/// Parse evaluation weights from a C string, refusing unknown terms so
+/// that misspelled ones are not silently ignored
+///
+/// # Safety
+/// `weights_json` must be null or a valid, NUL-terminated C string
+unsafe fn parse_weights(weights_json: *const c_char) -> Result<EvaluationWeights, String> {
+    if weights_json.is_null() {
+        return Err("weights_json is null".to_string());
+    }
+
+    let json = CStr::from_ptr(weights_json)
+        .to_str()
+        .map_err(|e| format!("weights are not valid UTF-8: {}", e))?;
+    EvaluationWeights::from_json(json, UnknownFields::Reject).map_err(|e| format!("invalid weights: {}", e))
+}
+
+/// Log history moves whose confidence does not fit their claim's boldness
+fn warn_inconsistent_moves(state: &GameState) {
+    for (index, m) in state.move_history.iter().enumerate() {
+        let score = m.consistency_score();
+        if score < CONSISTENCY_WARNING_THRESHOLD {
+            instrument::ffi_warning(
+                "FFI",
+                format!(
+                    "move {} has confidence {} inconsistent with its claim (consistency {:.2})",
+                    index, m.confidence, score
+                ),
+            );
+        }
+    }
+}
+
+/// Enable or disable warnings about externally supplied moves whose
+/// confidence and claim boldness disagree. Off by default; useful when
+/// debugging hand-crafted states.
+#[cfg_attr(not(target_arch = "wasm32"), no_mangle)]
+pub extern "C" fn set_move_consistency_warnings(enabled: bool) {
+    WARN_INCONSISTENT_MOVES.store(enabled, Ordering::Relaxed);
+}
+
+/// Cut every claim description in the state down to `max_chars` characters.
+/// The search never reads descriptions, so this does not change results.
+pub(crate) fn truncate_descriptions(state: &mut GameState, max_chars: usize) {
+    let claims = state
+        .current_claim
+        .iter_mut()
+        .chain(state.move_history.iter_mut().filter_map(|m| m.claim.as_mut()));
+
+    for claim in claims {
+        if let Some((end, _)) = claim.description.char_indices().nth(max_chars) {
+            instrument::ffi_warning(
+                "FFI",
+                format!("truncating claim description of {} bytes", claim.description.len()),
+            );
+            claim.description.truncate(end);
+        }
+    }
+}
+
+/// Set the maximum claim description length, in characters, accepted by
+/// later calls. Longer descriptions are truncated before searching.
+#[cfg_attr(not(target_arch = "wasm32"), no_mangle)]
+pub extern "C" fn set_max_description_length(max_chars: usize) {
+    MAX_DESCRIPTION_LEN.store(max_chars, Ordering::Relaxed);
+}
+
 /// Free memory allocated by search_optimal_move
 /// 
 /// # Safety
 /// This function is unsafe because it deals with raw pointers
 /// The pointer must have been created by search_optimal_move
-#[no_mangle]
+#[cfg_attr(not(target_arch = "wasm32"), no_mangle)]
 pub unsafe extern "C" fn free_result_string(s: *mut c_char) {
     if !s.is_null() {
         // Reconstruct the CString and let it drop
@@ -98,59 +433,359 @@ pub unsafe extern "C" fn free_result_string(s: *mut c_char) {
     }
 }
 
+/// Same as `search_optimal_move`, but with bincode instead of JSON on both
+/// sides, which is much cheaper to parse for large move histories. Reads
+/// a bincode-encoded `GameState` from the `state_len` bytes at `state`
+/// and returns a bincode-encoded `crate::SearchResult`, the full result
+/// rather than the JSON summary, storing its length in `out_len`.
+/// Returns null on error, with details from `get_last_error`; free the
+/// result with `free_result_bytes`.
+///
+/// # Safety
+/// `state` must point to `state_len` readable bytes and `out_len` must be
+/// valid for writes
+#[cfg_attr(not(target_arch = "wasm32"), no_mangle)]
+pub unsafe extern "C" fn search_optimal_move_bincode(
+    state: *const u8,
+    state_len: usize,
+    max_depth: u8,
+    player_id: u8,
+    out_len: *mut usize,
+) -> *mut u8 {
+    clear_last_error();
+    guard_panics("search", std::ptr::null_mut(), || {
+        search_bincode(state, state_len, max_depth, player_id, out_len)
+    })
+}
+
+unsafe fn search_bincode(
+    state: *const u8,
+    state_len: usize,
+    max_depth: u8,
+    player_id: u8,
+    out_len: *mut usize,
+) -> *mut u8 {
+    if state.is_null() || out_len.is_null() {
+        record_error("null_pointer", "state or out_len is null");
+        return std::ptr::null_mut();
+    }
+
+    let mut state: GameState = match bincode::deserialize(std::slice::from_raw_parts(state, state_len)) {
+        Ok(s) => s,
+        Err(e) => {
+            record_error("parse", format!("bincode parse error: {}", e));
+            return std::ptr::null_mut();
+        }
+    };
+    if let Err(e) = state.validate() {
+        record_error("invalid_state", e);
+        return std::ptr::null_mut();
+    }
+    truncate_descriptions(&mut state, MAX_DESCRIPTION_LEN.load(Ordering::Relaxed));
+
+    let player = match Player::from_id(player_id) {
+        Some(p) => p,
+        None => {
+            record_error("invalid_argument", format!("invalid player_id {}", player_id));
+            return std::ptr::null_mut();
+        }
+    };
+
+    let result = AlphaBetaSearch::new(max_depth, true).search(&state, player);
+    let bytes = match bincode::serialize(&result) {
+        Ok(bytes) => bytes.into_boxed_slice(),
+        Err(e) => {
+            record_error("serialization", format!("bincode serialization error: {}", e));
+            return std::ptr::null_mut();
+        }
+    };
+
+    *out_len = bytes.len();
+    Box::into_raw(bytes) as *mut u8
+}
+
+/// Free a result of `search_optimal_move_bincode`
+///
+/// # Safety
+/// `bytes` must be null or a pointer returned by
+/// `search_optimal_move_bincode`, with the length it stored in `out_len`
+#[cfg_attr(not(target_arch = "wasm32"), no_mangle)]
+pub unsafe extern "C" fn free_result_bytes(bytes: *mut u8, len: usize) {
+    if !bytes.is_null() {
+        drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(bytes, len)));
+    }
+}
+
 /// Evaluate a game state (C-style FFI)
 /// 
 /// # Safety
 /// This function is unsafe because it deals with raw pointers
-#[no_mangle]
+#[cfg_attr(not(target_arch = "wasm32"), no_mangle)]
 pub unsafe extern "C" fn evaluate_state(
     game_state_json: *const c_char,
     player_id: u8,
 ) -> f64 {
+    clear_last_error();
+    guard_panics("evaluation", 0.0, || evaluate_json(game_state_json, player_id))
+}
+
+unsafe fn evaluate_json(game_state_json: *const c_char, player_id: u8) -> f64 {
     if game_state_json.is_null() {
-        eprintln!("[FFI] Error: Null game_state_json pointer");
+        record_error("null_pointer", "game_state_json is null");
         return 0.0;
     }
 
-    let c_str = match CStr::from_ptr(game_state_json).to_str() {
+    let state = match parse_state(game_state_json) {
+        Some(s) => s,
+        None => return 0.0,
+    };
+
+    let player = match Player::from_id(player_id) {
+        Some(p) => p,
+        None => {
+            record_error("invalid_argument", format!("invalid player_id {}", player_id));
+            return 0.0;
+        }
+    };
+
+    let evaluator = crate::evaluation::Evaluator::new();
+    evaluator.evaluate(&state, player)
+}
+
+/// Evaluate every state in a JSON array (C-style FFI).
+/// Returns a JSON array of evaluations in the same order, or null on error.
+/// Runs on the pool set up by `configure_threads`, if any, and on the
+/// global rayon pool otherwise.
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers
+#[cfg_attr(not(target_arch = "wasm32"), no_mangle)]
+pub unsafe extern "C" fn evaluate_states_batch(
+    game_states_json: *const c_char,
+    player_id: u8,
+) -> *mut c_char {
+    clear_last_error();
+    guard_panics("batch evaluation", std::ptr::null_mut(), || {
+        evaluate_batch_json(game_states_json, player_id)
+    })
+}
+
+unsafe fn evaluate_batch_json(game_states_json: *const c_char, player_id: u8) -> *mut c_char {
+    use rayon::prelude::*;
+
+    if game_states_json.is_null() {
+        record_error("null_pointer", "game_states_json is null");
+        return std::ptr::null_mut();
+    }
+
+    let c_str = match CStr::from_ptr(game_states_json).to_str() {
         Ok(s) => s,
         Err(e) => {
-            eprintln!("[FFI] Error converting C string: {}", e);
-            return 0.0;
+            record_error("invalid_input", format!("input is not valid UTF-8: {}", e));
+            return std::ptr::null_mut();
         }
     };
 
-    let state: GameState = match serde_json::from_str(c_str) {
+    let mut states: Vec<GameState> = match serde_json::from_str(c_str) {
         Ok(s) => s,
         Err(e) => {
-            eprintln!("[FFI] JSON parse error: {}", e);
-            return 0.0;
+            record_error("parse", format!("JSON parse error: {}", e));
+            return std::ptr::null_mut();
         }
     };
 
-    let player = if player_id == 1 {
-        Player::Player1
-    } else {
-        Player::Player2
+    let player = match Player::from_id(player_id) {
+        Some(p) => p,
+        None => {
+            record_error("invalid_argument", format!("invalid player_id {}", player_id));
+            return std::ptr::null_mut();
+        }
     };
 
+    if let Some((index, e)) = states
+        .iter()
+        .enumerate()
+        .find_map(|(index, state)| state.validate().err().map(|e| (index, e)))
+    {
+        record_error("invalid_state", format!("state {}: {}", index, e));
+        return std::ptr::null_mut();
+    }
+
+    let max_chars = MAX_DESCRIPTION_LEN.load(Ordering::Relaxed);
+    for state in &mut states {
+        truncate_descriptions(state, max_chars);
+    }
+
     let evaluator = crate::evaluation::Evaluator::new();
-    evaluator.evaluate(&state, player)
+    let evaluate = || -> Vec<f64> {
+        states.par_iter().map(|state| evaluator.evaluate(state, player)).collect()
+    };
+    let evaluations = match BATCH_POOL.get() {
+        Some(pool) => pool.install(evaluate),
+        None => evaluate(),
+    };
+
+    to_json_c_string(&evaluations)
+}
+
+/// Legal moves for `player_id` in a state, as a JSON array of moves
+/// (C-style FFI). Empty when nothing can be played, e.g. in the resolution
+/// phase. Returns null on error; free the result with `free_result_string`.
+///
+/// # Safety
+/// This function is unsafe because it deals with raw pointers
+#[cfg_attr(not(target_arch = "wasm32"), no_mangle)]
+pub unsafe extern "C" fn get_legal_moves(
+    game_state_json: *const c_char,
+    player_id: u8,
+) -> *mut c_char {
+    clear_last_error();
+    guard_panics("move generation", std::ptr::null_mut(), || legal_moves_json(game_state_json, player_id))
+}
+
+unsafe fn legal_moves_json(game_state_json: *const c_char, player_id: u8) -> *mut c_char {
+    if game_state_json.is_null() {
+        record_error("null_pointer", "game_state_json is null");
+        return std::ptr::null_mut();
+    }
+
+    let state = match parse_state(game_state_json) {
+        Some(s) => s,
+        None => return std::ptr::null_mut(),
+    };
+
+    let player = match Player::from_id(player_id) {
+        Some(p) => p,
+        None => {
+            record_error("invalid_argument", format!("invalid player_id {}", player_id));
+            return std::ptr::null_mut();
+        }
+    };
+
+    let moves = GameTree::new(state.clone()).generate_moves(&state, player);
+    to_json_c_string(&moves)
+}
+
+/// Serialize a value into a newly allocated C string, or null on error
+fn to_json_c_string<T: Serialize>(value: &T) -> *mut c_char {
+    let json = match serde_json::to_string(value) {
+        Ok(json) => json,
+        Err(e) => {
+            record_error("serialization", format!("JSON serialization error: {}", e));
+            return std::ptr::null_mut();
+        }
+    };
+
+    match CString::new(json) {
+        Ok(c_string) => c_string.into_raw(),
+        Err(e) => {
+            record_error("serialization", format!("CString creation error: {}", e));
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// Set up a dedicated pool of `num_threads` threads for batch operations.
+/// Can only be done once per process, typically right after
+/// `initialize_optimizer`. Returns 0 on success, 1 if a pool was already
+/// configured and 2 if the pool could not be created.
+#[cfg_attr(not(target_arch = "wasm32"), no_mangle)]
+pub extern "C" fn configure_threads(num_threads: u32) -> i32 {
+    clear_last_error();
+
+    if BATCH_POOL.get().is_some() {
+        record_error("thread_pool", "batch thread pool already configured");
+        return 1;
+    }
+
+    let pool = match rayon::ThreadPoolBuilder::new()
+        .num_threads(num_threads as usize)
+        .thread_name(|i| format!("optimizer-batch-{}", i))
+        .build()
+    {
+        Ok(pool) => pool,
+        Err(e) => {
+            record_error("thread_pool", format!("thread pool creation error: {}", e));
+            return 2;
+        }
+    };
+
+    match BATCH_POOL.set(pool) {
+        Ok(()) => 0,
+        Err(_) => 1,
+    }
+}
+
+/// Details of the latest failure on the calling thread, as
+/// `"<category>: <message>"`, or null if the latest call succeeded.
+/// Categories are `null_pointer`, `invalid_input`, `parse`,
+/// `invalid_state`, `invalid_argument`, `serialization`, `thread_pool`
+/// and `panic`.
+///
+/// Every fallible function clears the error on entry and records one when
+/// it fails, so check right after the call that returned null or an error
+/// code. The string belongs to the library: do not free it. It stays valid
+/// until the next call into the library on the same thread; copy it to
+/// keep it longer.
+#[cfg_attr(not(target_arch = "wasm32"), no_mangle)]
+pub extern "C" fn get_last_error() -> *const c_char {
+    LAST_ERROR.with(|error| {
+        error
+            .borrow()
+            .as_ref()
+            .map_or(std::ptr::null(), |message| message.as_ptr())
+    })
+}
+
+/// Log an error and keep it for `get_last_error`
+fn record_error(category: &str, message: impl std::fmt::Display) {
+    let message = format!("{}: {}", category, message);
+    instrument::ffi_error("FFI", &message);
+
+    // A message cannot contain a NUL, but never fail while reporting a failure
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|error| *error.borrow_mut() = Some(message));
+}
+
+/// Run an entry point's body, turning a panic into `fallback` and a
+/// recorded error: unwinding into the host across the FFI boundary is
+/// undefined behavior. This only works in unwinding builds; with
+/// `panic = "abort"` a panic ends the process before it can be caught.
+fn guard_panics<T>(operation: &str, fallback: T, body: impl FnOnce() -> T) -> T {
+    match std::panic::catch_unwind(std::panic::AssertUnwindSafe(body)) {
+        Ok(value) => value,
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown cause".to_string());
+            record_error("panic", format!("{} panicked: {}", operation, message));
+            fallback
+        }
+    }
+}
+
+fn clear_last_error() {
+    LAST_ERROR.with(|error| *error.borrow_mut() = None);
 }
 
 /// Get library version
-#[no_mangle]
+#[cfg_attr(not(target_arch = "wasm32"), no_mangle)]
 pub extern "C" fn get_version() -> *const c_char {
     static VERSION: &str = "1.0.0\0";
     VERSION.as_ptr() as *const c_char
 }
 
 /// Initialize the library (for any setup needed)
-#[no_mangle]
+#[cfg_attr(not(target_arch = "wasm32"), no_mangle)]
 pub extern "C" fn initialize_optimizer() -> i32 {
-    // Perform any initialization
-    // Return 0 for success, non-zero for error
-    0
+    clear_last_error();
+    guard_panics("initialization", 1, || {
+        // Perform any initialization
+        // Return 0 for success, non-zero for error
+        0
+    })
 }
 
 // JNI Bindings (only compiled when 'jni' feature is enabled)
@@ -173,7 +808,7 @@ pub mod jni_bindings {
         let json_str: String = match env.get_string(game_state_json) {
             Ok(s) => s.into(),
             Err(e) => {
-                eprintln!("[JNI] Error getting string: {:?}", e);
+                crate::instrument::ffi_error("JNI", format!("error getting string: {:?}", e));
                 return JString::default().into_inner();
             }
         };
@@ -183,7 +818,7 @@ pub mod jni_bindings {
             let c_json = match std::ffi::CString::new(json_str) {
                 Ok(s) => s,
                 Err(e) => {
-                    eprintln!("[JNI] CString creation error: {}", e);
+                    crate::instrument::ffi_error("JNI", format!("CString creation error: {}", e));
                     return JString::default().into_inner();
                 }
             };
@@ -195,7 +830,7 @@ pub mod jni_bindings {
             );
             
             if result_ptr.is_null() {
-                eprintln!("[JNI] Search returned null");
+                crate::instrument::ffi_error("JNI", "search returned null");
                 return JString::default().into_inner();
             }
             
@@ -209,7 +844,7 @@ pub mod jni_bindings {
         match env.new_string(result) {
             Ok(s) => s.into_inner(),
             Err(e) => {
-                eprintln!("[JNI] Error creating JString: {:?}", e);
+                crate::instrument::ffi_error("JNI", format!("error creating JString: {:?}", e));
                 JString::default().into_inner()
             }
         }
@@ -226,7 +861,7 @@ pub mod jni_bindings {
         let json_str: String = match env.get_string(game_state_json) {
             Ok(s) => s.into(),
             Err(e) => {
-                eprintln!("[JNI] Error getting string: {:?}", e);
+                crate::instrument::ffi_error("JNI", format!("error getting string: {:?}", e));
                 return 0.0;
             }
         };
@@ -235,7 +870,7 @@ pub mod jni_bindings {
             let c_json = match std::ffi::CString::new(json_str) {
                 Ok(s) => s,
                 Err(e) => {
-                    eprintln!("[JNI] CString creation error: {}", e);
+                    crate::instrument::ffi_error("JNI", format!("CString creation error: {}", e));
                     return 0.0;
                 }
             };
@@ -254,10 +889,82 @@ pub mod jni_bindings {
     }
 }
 
+// Browser bindings (only compiled when 'wasm' feature is enabled)
+#[cfg(feature = "wasm")]
+pub mod wasm_bindings {
+    use super::{truncate_descriptions, SearchResult, MAX_DESCRIPTION_LEN};
+    use crate::{AlphaBetaSearch, GameState, Player};
+    use std::sync::atomic::Ordering;
+    use wasm_bindgen::prelude::*;
+
+    /// Parse and validate a JSON game state, truncating claim descriptions
+    /// like the C entry points do
+    fn state_from_json(state_json: &str) -> Result<GameState, String> {
+        let mut state = serde_json::from_str::<GameState>(state_json).map_err(|e| format!("JSON parse error: {}", e))?;
+        state.validate().map_err(|e| e.to_string())?;
+        truncate_descriptions(&mut state, MAX_DESCRIPTION_LEN.load(Ordering::Relaxed));
+        Ok(state)
+    }
+
+    /// Search a JSON game state with alpha-beta and return the same JSON
+    /// `SearchResult` as the C entry points, or `{"error": "..."}` when the
+    /// input is rejected. Searches run serially on wasm32.
+    #[wasm_bindgen(js_name = search_optimal_move)]
+    pub fn search_optimal_move(state_json: &str, max_depth: u8, player: u8) -> String {
+        let result = state_from_json(state_json).and_then(|state| {
+            let player = Player::from_id(player).ok_or_else(|| format!("invalid player {}", player))?;
+            let result = AlphaBetaSearch::new(max_depth, true).search(&state, player);
+            serde_json::to_string(&SearchResult::from(result)).map_err(|e| format!("JSON serialization error: {}", e))
+        });
+
+        result.unwrap_or_else(|error| serde_json::json!({ "error": error }).to_string())
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_wasm_search() {
+            let json = r#"{"round":1,"phase":"Claim","player1_trust":50,"player2_trust":50,"current_claim":null,"move_history":[]}"#;
+
+            let result: SearchResult = serde_json::from_str(&search_optimal_move(json, 2, 1)).unwrap();
+            assert_eq!(result.best_move.unwrap().action, "MakeClaim");
+
+            let error: serde_json::Value = serde_json::from_str(&search_optimal_move("{", 2, 1)).unwrap();
+            assert!(error["error"].as_str().unwrap().starts_with("JSON parse error"));
+
+            let mut state: GameState = serde_json::from_str(json).unwrap();
+            state.phase = crate::Phase::Challenge;
+            state.current_claim = Some(crate::Claim {
+                claim_type: crate::ClaimType::Prediction,
+                boldness: 0.6,
+                is_bluff: true,
+                description: "x".repeat(1 << 20),
+            });
+            let long_json = serde_json::to_string(&state).unwrap();
+
+            let parsed = state_from_json(&long_json).unwrap();
+            assert_eq!(parsed.current_claim.unwrap().description.len(), crate::ffi::DEFAULT_MAX_DESCRIPTION_LEN);
+            let result: SearchResult = serde_json::from_str(&search_optimal_move(&long_json, 2, 2)).unwrap();
+            assert!(result.best_move.is_some());
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Evaluator that fails on every state
+    struct PanickingEvaluator;
+
+    impl StateEvaluator for PanickingEvaluator {
+        fn evaluate(&self, _state: &GameState, _player: Player) -> f64 {
+            panic!("forced search panic");
+        }
+    }
+
     #[test]
     fn test_ffi_search() {
         let json = r#"{"round":1,"phase":"Claim","player1_trust":50,"player2_trust":50,"current_claim":null,"move_history":[]}"#;
@@ -276,6 +983,381 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_ffi_move_result_claim_details() {
+        let search = |json: &str| unsafe {
+            let c_json = std::ffi::CString::new(json).unwrap();
+            let result = search_optimal_move_seeded(c_json.as_ptr(), 2, 1, 7);
+            assert!(!result.is_null());
+            let value: serde_json::Value =
+                serde_json::from_str(&std::ffi::CStr::from_ptr(result).to_string_lossy()).unwrap();
+            free_result_string(result);
+            value["best_move"].clone()
+        };
+
+        let claim = search(r#"{"round":1,"phase":"Claim","player1_trust":50,"player2_trust":50,"current_claim":null,"move_history":[]}"#);
+        assert_eq!(claim["action"], "MakeClaim");
+        assert!(claim["claim_type"].is_string());
+        assert!(claim["boldness"].is_number());
+        assert!(claim["is_bluff"].is_boolean());
+        assert!(claim["description"].is_string());
+
+        // Player 1 answering a claim: no claim fields at all
+        let claim_json = r#"{"description":"x","claim_type":"Prediction","boldness":0.8,"is_bluff":true}"#;
+        let reply = search(&format!(
+            r#"{{"round":1,"phase":"Challenge","player1_trust":50,"player2_trust":50,"current_claim":{},"move_history":[]}}"#,
+            claim_json
+        ));
+        assert_ne!(reply["action"], "MakeClaim");
+        let keys: Vec<_> = reply.as_object().unwrap().keys().collect();
+        assert_eq!(keys, ["action", "confidence"]);
+
+        // Results from before the claim fields still parse
+        let old: MoveResult = serde_json::from_str(r#"{"action":"Accept","confidence":0.5}"#).unwrap();
+        assert!(old.claim_type.is_none() && old.description.is_none());
+    }
+
+    #[test]
+    fn test_ffi_last_error() {
+        let last_error = || unsafe {
+            let error = get_last_error();
+            (!error.is_null()).then(|| std::ffi::CStr::from_ptr(error).to_string_lossy().into_owned())
+        };
+
+        let malformed = std::ffi::CString::new(r#"{"round": 1, "phase": "#).unwrap();
+        unsafe {
+            assert!(search_optimal_move(malformed.as_ptr(), 2, 1).is_null());
+        }
+        let error = last_error().unwrap();
+        assert!(error.starts_with("parse: "), "{error}");
+
+        unsafe {
+            assert!(search_optimal_move(std::ptr::null(), 2, 1).is_null());
+        }
+        assert!(last_error().unwrap().starts_with("null_pointer: "));
+
+        // A successful call clears it
+        let json = std::ffi::CString::new(
+            r#"{"round":1,"phase":"Claim","player1_trust":50,"player2_trust":50,"current_claim":null,"move_history":[]}"#,
+        )
+        .unwrap();
+        unsafe {
+            let result = search_optimal_move(json.as_ptr(), 1, 1);
+            assert!(!result.is_null());
+            free_result_string(result);
+        }
+        assert_eq!(last_error(), None);
+    }
+
+    #[test]
+    fn test_ffi_search_panic_is_caught() {
+        let json = std::ffi::CString::new(
+            r#"{"round":1,"phase":"Claim","player1_trust":50,"player2_trust":50,"current_claim":null,"move_history":[]}"#,
+        )
+        .unwrap();
+
+        let options = SearchOptions {
+            evaluator: Arc::new(PanickingEvaluator),
+            ..SearchOptions::new(2)
+        };
+        let result = unsafe { run_search(json.as_ptr(), 1, options) };
+
+        assert!(result.is_null());
+        let error = unsafe { std::ffi::CStr::from_ptr(get_last_error()) };
+        assert_eq!(error.to_str().unwrap(), "panic: search panicked: forced search panic");
+
+        // Later calls are unaffected
+        let result = unsafe { search_optimal_move(json.as_ptr(), 2, 1) };
+        assert!(!result.is_null());
+        unsafe { free_result_string(result) };
+    }
+
+    #[test]
+    fn test_ffi_minimax_search() {
+        let json = std::ffi::CString::new(
+            r#"{"round":1,"phase":"Claim","player1_trust":50,"player2_trust":50,"current_claim":null,"move_history":[]}"#,
+        )
+        .unwrap();
+
+        let parse = |result: *mut c_char| unsafe {
+            assert!(!result.is_null());
+            let parsed: SearchResult =
+                serde_json::from_str(&std::ffi::CStr::from_ptr(result).to_string_lossy()).unwrap();
+            free_result_string(result);
+            parsed
+        };
+        let minimax = parse(unsafe { search_optimal_move_minimax(json.as_ptr(), 2, 1) });
+        let alpha_beta = parse(unsafe { search_optimal_move(json.as_ptr(), 2, 1) });
+
+        assert_eq!(minimax.depth_reached, 2);
+        assert_eq!(minimax.best_move.unwrap().action, "MakeClaim");
+        assert_eq!(alpha_beta.best_move.unwrap().action, "MakeClaim");
+        // Nothing is pruned
+        assert!(minimax.nodes_explored >= alpha_beta.nodes_explored);
+    }
+
+    #[test]
+    fn test_ffi_search_with_weights() {
+        // Player 1 is near victory, which the weights below value differently
+        let json = std::ffi::CString::new(
+            r#"{"round":3,"phase":"Claim","player1_trust":92,"player2_trust":40,"current_claim":null,"move_history":[]}"#,
+        )
+        .unwrap();
+        let search = |weights: Option<&str>| unsafe {
+            let weights = weights.map(|w| std::ffi::CString::new(w).unwrap());
+            let pointer = weights.as_ref().map_or(std::ptr::null(), |w| w.as_ptr());
+            let result = search_with_weights(json.as_ptr(), pointer, 1, 1);
+            assert!(!result.is_null());
+            let parsed: SearchResult =
+                serde_json::from_str(&std::ffi::CStr::from_ptr(result).to_string_lossy()).unwrap();
+            free_result_string(result);
+            parsed
+        };
+
+        let default = search(Some("{}"));
+        assert!(default.weights_error.is_none());
+
+        let custom = search(Some(r#"{"trust_differential": 0.0, "trust_absolute": 0.0, "position_advantage": 2.0}"#));
+        assert!(custom.weights_error.is_none());
+        assert_ne!(custom.evaluation, default.evaluation);
+
+        // Bad weights fall back to the defaults and say so
+        for bad in [Some("{\"trust_diferential\": 2.0}"), Some("not json"), None] {
+            let fallback = search(bad);
+            assert!(fallback.weights_error.is_some());
+            assert_eq!(fallback.evaluation, default.evaluation);
+        }
+    }
+
+    #[test]
+    fn test_ffi_search_with_risk_profile() {
+        let json = std::ffi::CString::new(
+            r#"{"round":1,"phase":"Claim","player1_trust":50,"player2_trust":50,"current_claim":null,"move_history":[]}"#,
+        )
+        .unwrap();
+        let boldness = |profile: u8| unsafe {
+            let result = search_with_risk_profile(json.as_ptr(), 2, 1, profile);
+            assert!(!result.is_null());
+            let parsed: SearchResult =
+                serde_json::from_str(&std::ffi::CStr::from_ptr(result).to_string_lossy()).unwrap();
+            free_result_string(result);
+            parsed.best_move.unwrap().boldness.unwrap()
+        };
+
+        assert!(RiskProfile::Cautious.boldness_levels().contains(&boldness(0)));
+        assert!(RiskProfile::Aggressive.boldness_levels().contains(&boldness(2)));
+
+        unsafe {
+            assert!(search_with_risk_profile(json.as_ptr(), 2, 1, 3).is_null());
+            let error = std::ffi::CStr::from_ptr(get_last_error()).to_string_lossy();
+            assert!(error.contains("risk_profile"), "{error}");
+        }
+    }
+
+    #[test]
+    fn test_ffi_bincode_round_trip() {
+        let state = GameState {
+            round: 2,
+            phase: crate::Phase::Claim,
+            player1_trust: 55,
+            player2_trust: 50,
+            current_claim: None,
+            move_history: Vec::new(),
+        };
+        let encoded = bincode::serialize(&state).unwrap();
+
+        let mut len = 0;
+        let result = unsafe { search_optimal_move_bincode(encoded.as_ptr(), encoded.len(), 3, 1, &mut len) };
+        assert!(!result.is_null());
+        let bytes = unsafe { std::slice::from_raw_parts(result, len) };
+        let decoded: crate::SearchResult = bincode::deserialize(bytes).unwrap();
+        unsafe { free_result_bytes(result, len) };
+
+        assert_eq!(decoded.best_move.player, Player::Player1);
+        assert_eq!(decoded.depth_reached, 3);
+        assert_eq!(decoded.principal_variation.first(), Some(&decoded.best_move));
+
+        // Truncated input is a parse error, not a crash
+        let result = unsafe { search_optimal_move_bincode(encoded.as_ptr(), 3, 3, 1, &mut len) };
+        assert!(result.is_null());
+        let error = unsafe { std::ffi::CStr::from_ptr(get_last_error()) };
+        assert!(error.to_str().unwrap().starts_with("parse: "));
+    }
+
+    #[test]
+    fn test_ffi_search_pretty() {
+        let json = r#"{"round":1,"phase":"Claim","player1_trust":50,"player2_trust":50,"current_claim":null,"move_history":[]}"#;
+        let c_json = std::ffi::CString::new(json).unwrap();
+
+        unsafe {
+            let compact = search_optimal_move(c_json.as_ptr(), 2, 1);
+            let pretty = search_optimal_move_pretty(c_json.as_ptr(), 2, 1);
+            assert!(!compact.is_null());
+            assert!(!pretty.is_null());
+
+            let compact_str = std::ffi::CStr::from_ptr(compact).to_string_lossy().into_owned();
+            let pretty_str = std::ffi::CStr::from_ptr(pretty).to_string_lossy().into_owned();
+            assert!(!compact_str.contains('\n'));
+            assert!(pretty_str.contains('\n'));
+
+            let compact_value: serde_json::Value = serde_json::from_str(&compact_str).unwrap();
+            let pretty_value: serde_json::Value = serde_json::from_str(&pretty_str).unwrap();
+            let compact_keys: Vec<_> = compact_value.as_object().unwrap().keys().collect();
+            let pretty_keys: Vec<_> = pretty_value.as_object().unwrap().keys().collect();
+            assert_eq!(compact_keys, pretty_keys);
+
+            free_result_string(compact);
+            free_result_string(pretty);
+        }
+    }
+
+    #[test]
+    fn test_ffi_search_seeded_is_reproducible() {
+        let json = r#"{"round":1,"phase":"Claim","player1_trust":50,"player2_trust":50,"current_claim":null,"move_history":[]}"#;
+        let c_json = std::ffi::CString::new(json).unwrap();
+
+        let run = || unsafe {
+            let result = search_optimal_move_seeded(c_json.as_ptr(), 4, 1, 42);
+            assert!(!result.is_null());
+            let result_str = std::ffi::CStr::from_ptr(result).to_string_lossy().into_owned();
+            free_result_string(result);
+
+            // Timing is the only field allowed to differ between runs
+            let mut value: serde_json::Value = serde_json::from_str(&result_str).unwrap();
+            value.as_object_mut().unwrap().remove("time_ms");
+            value
+        };
+
+        assert_eq!(run(), run());
+    }
+
+    #[test]
+    fn test_ffi_truncates_long_description() {
+        let state = GameState {
+            round: 1,
+            phase: crate::Phase::Challenge,
+            player1_trust: 50,
+            player2_trust: 50,
+            current_claim: Some(crate::Claim {
+                claim_type: crate::ClaimType::Prediction,
+                boldness: 0.6,
+                is_bluff: true,
+                description: "x".repeat(1 << 20),
+            }),
+            move_history: Vec::new(),
+        };
+        let c_json = std::ffi::CString::new(serde_json::to_string(&state).unwrap()).unwrap();
+
+        unsafe {
+            let parsed = parse_state(c_json.as_ptr()).unwrap();
+            let claim = parsed.current_claim.unwrap();
+            assert_eq!(claim.description.len(), DEFAULT_MAX_DESCRIPTION_LEN);
+            assert!(claim.is_bluff);
+
+            let result = search_optimal_move(c_json.as_ptr(), 3, 2);
+            assert!(!result.is_null());
+            free_result_string(result);
+        }
+    }
+
+    #[test]
+    fn test_truncate_descriptions_respects_char_boundaries() {
+        let mut state: GameState = serde_json::from_str(
+            r#"{"round":1,"phase":"Challenge","player1_trust":50,"player2_trust":50,"current_claim":{"claim_type":"Alliance","boldness":0.4,"is_bluff":false,"description":"ééé"},"move_history":[]}"#,
+        )
+        .unwrap();
+
+        truncate_descriptions(&mut state, 2);
+        assert_eq!(state.current_claim.unwrap().description, "éé");
+    }
+
+    #[test]
+    fn test_ffi_rejects_invalid_player_id() {
+        let json = r#"{"round":1,"phase":"Claim","player1_trust":50,"player2_trust":50,"current_claim":null,"move_history":[]}"#;
+        let c_json = std::ffi::CString::new(json).unwrap();
+
+        unsafe {
+            assert!(search_optimal_move(c_json.as_ptr(), 2, 0).is_null());
+            assert!(search_optimal_move(c_json.as_ptr(), 2, 3).is_null());
+            assert_eq!(evaluate_state(c_json.as_ptr(), 7), 0.0);
+        }
+    }
+
+    #[test]
+    fn test_ffi_rejects_out_of_range_state() {
+        let inputs = [
+            (r#"{"round":1,"phase":"Claim","player1_trust":50,"player2_trust":-99999,"current_claim":null,"move_history":[]}"#, "trust"),
+            (r#"{"round":1,"phase":"Challenge","player1_trust":50,"player2_trust":50,"current_claim":{"description":"","claim_type":"Alliance","boldness":-2.0,"is_bluff":false},"move_history":[]}"#, "boldness"),
+            (r#"{"round":2,"phase":"Claim","player1_trust":50,"player2_trust":50,"current_claim":null,"move_history":[{"action":"Accept","player":"Player2","claim":null,"confidence":40.0}]}"#, "confidence"),
+        ];
+
+        for (json, field) in inputs {
+            let c_json = std::ffi::CString::new(json).unwrap();
+            unsafe {
+                assert!(search_optimal_move(c_json.as_ptr(), 2, 1).is_null());
+                let error = std::ffi::CStr::from_ptr(get_last_error()).to_string_lossy();
+                assert!(error.starts_with("invalid_state: ") && error.contains(field), "{error}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_ffi_batch_on_configured_pool() {
+        assert_eq!(configure_threads(2), 0);
+        assert_eq!(BATCH_POOL.get().unwrap().current_num_threads(), 2);
+        assert_eq!(configure_threads(4), 1);
+
+        let states: Vec<GameState> = [(50, 50), (80, 30), (20, 70)]
+            .iter()
+            .map(|&(p1, p2)| GameState {
+                round: 1,
+                phase: crate::Phase::Claim,
+                player1_trust: p1,
+                player2_trust: p2,
+                current_claim: None,
+                move_history: Vec::new(),
+            })
+            .collect();
+        let c_json = std::ffi::CString::new(serde_json::to_string(&states).unwrap()).unwrap();
+
+        unsafe {
+            let result = evaluate_states_batch(c_json.as_ptr(), 1);
+            assert!(!result.is_null());
+            let result_str = std::ffi::CStr::from_ptr(result).to_string_lossy().into_owned();
+            free_result_string(result);
+
+            let evaluations: Vec<f64> = serde_json::from_str(&result_str).unwrap();
+            let evaluator = crate::evaluation::Evaluator::new();
+            let expected: Vec<f64> = states.iter().map(|s| evaluator.evaluate(s, Player::Player1)).collect();
+            assert_eq!(evaluations, expected);
+        }
+    }
+
+    #[test]
+    fn test_ffi_legal_moves() {
+        let legal_moves = |json: &str| -> Vec<Move> {
+            let c_json = std::ffi::CString::new(json).unwrap();
+            unsafe {
+                let result = get_legal_moves(c_json.as_ptr(), 1);
+                assert!(!result.is_null());
+                let result_str = std::ffi::CStr::from_ptr(result).to_string_lossy().into_owned();
+                free_result_string(result);
+                serde_json::from_str(&result_str).unwrap()
+            }
+        };
+
+        let claims = legal_moves(r#"{"round":1,"phase":"Claim","player1_trust":50,"player2_trust":50,"current_claim":null,"move_history":[]}"#);
+        assert_eq!(claims.len(), 16);
+        assert!(claims.iter().all(|m| m.action == crate::Action::MakeClaim && m.player == Player::Player1));
+
+        let responses = legal_moves(r#"{"round":1,"phase":"Challenge","player1_trust":50,"player2_trust":50,"current_claim":{"description":"","claim_type":"Alliance","boldness":0.4,"is_bluff":false},"move_history":[]}"#);
+        let actions: Vec<_> = responses.iter().map(|m| m.action).collect();
+        assert_eq!(actions, vec![crate::Action::Challenge, crate::Action::Accept]);
+
+        let none = legal_moves(r#"{"round":1,"phase":"Resolution","player1_trust":50,"player2_trust":50,"current_claim":null,"move_history":[]}"#);
+        assert!(none.is_empty());
+    }
+
     #[test]
     fn test_ffi_evaluate() {
         let json = r#"{"round":1,"phase":"Claim","player1_trust":50,"player2_trust":50,"current_claim":null,"move_history":[]}"#;
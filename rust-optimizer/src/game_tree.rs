@@ -1,5 +1,128 @@
-use crate::{GameState, Move, Player, Action, Phase};
-use std::collections::HashMap;
+use crate::{Claim, GameState, Move, Player, Action, Phase, StableHasher};
+use crate::profile::RiskProfile;
+use crate::rules::{GameOutcome, GameRules};
+use std::hash::{Hash, Hasher};
+
+/// Times the same position must come up on a line of play to draw the game
+pub const REPETITION_LIMIT: usize = 3;
+
+/// Boldness above which generated claims are bluffs, unless configured
+const DEFAULT_BLUFF_THRESHOLD: f64 = 0.5;
+
+/// What `GameTree::make_move` changed, for `GameTree::undo_move` to take
+/// back: the phase and round before the move, the trust it moved and the
+/// claim it replaced. The move itself was pushed onto the history.
+#[derive(Debug, Clone)]
+pub struct UndoToken {
+    phase: Phase,
+    round: u8,
+    player1_delta: i32,
+    player2_delta: i32,
+    claim: Option<Claim>,
+}
+
+/// Returned by `GameTree::add_child` once the tree holds as many nodes as
+/// it was given capacity for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TreeFull {
+    pub max_nodes: usize,
+}
+
+impl std::fmt::Display for TreeFull {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "game tree is full at {} nodes", self.max_nodes)
+    }
+}
+
+impl std::error::Error for TreeFull {}
+
+/// Why `ClaimLevels::new` rejected its settings
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InvalidClaimLevels {
+    /// No boldness levels, so no claim could be made
+    Empty,
+    /// A boldness level or the bluff threshold outside [0, 1]
+    OutOfRange(f64),
+    /// A boldness level between tenths, which positions are hashed in
+    OffGrid(f64),
+}
+
+impl std::fmt::Display for InvalidClaimLevels {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvalidClaimLevels::Empty => write!(f, "no boldness levels given"),
+            InvalidClaimLevels::OutOfRange(value) => write!(f, "{} is outside [0, 1]", value),
+            InvalidClaimLevels::OffGrid(value) => write!(f, "{} is not a multiple of 0.1", value),
+        }
+    }
+}
+
+impl std::error::Error for InvalidClaimLevels {}
+
+/// Boldness levels to generate claims at, whatever the players' risk
+/// profiles, and the boldness beyond which those claims are bluffs
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClaimLevels {
+    boldness_levels: Vec<f64>,
+    bluff_threshold: f64,
+}
+
+impl ClaimLevels {
+    /// Levels and threshold must lie in [0, 1]. Levels must also be
+    /// multiples of 0.1, since positions are hashed with boldness in
+    /// tenths and claims between two tenths would be taken for each other.
+    pub fn new(boldness_levels: Vec<f64>, bluff_threshold: f64) -> Result<Self, InvalidClaimLevels> {
+        if boldness_levels.is_empty() {
+            return Err(InvalidClaimLevels::Empty);
+        }
+        if let Some(&value) = boldness_levels
+            .iter()
+            .chain([&bluff_threshold])
+            .find(|value| !(0.0..=1.0).contains(*value))
+        {
+            return Err(InvalidClaimLevels::OutOfRange(value));
+        }
+        if let Some(&value) = boldness_levels.iter().find(|&&value| !crate::zobrist::on_boldness_grid(value)) {
+            return Err(InvalidClaimLevels::OffGrid(value));
+        }
+
+        Ok(Self {
+            boldness_levels,
+            bluff_threshold,
+        })
+    }
+
+    pub fn boldness_levels(&self) -> &[f64] {
+        &self.boldness_levels
+    }
+
+    pub fn bluff_threshold(&self) -> f64 {
+        self.bluff_threshold
+    }
+}
+
+/// Why `GameTree::apply_move` refused a move
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IllegalMove {
+    /// The action cannot be played in this phase
+    WrongPhase { phase: Phase, action: Action },
+    /// A claim move without a claim or with boldness outside [0, 1], or
+    /// another move carrying a claim
+    MalformedClaim,
+}
+
+impl std::fmt::Display for IllegalMove {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IllegalMove::WrongPhase { phase, action } => {
+                write!(f, "{:?} cannot be played in the {:?} phase", action, phase)
+            }
+            IllegalMove::MalformedClaim => write!(f, "move carries a missing or invalid claim"),
+        }
+    }
+}
+
+impl std::error::Error for IllegalMove {}
 
 /// Represents a node in the game tree
 #[derive(Debug, Clone)]
@@ -36,21 +159,105 @@ impl GameNode {
 /// Game tree for efficient state space exploration
 pub struct GameTree {
     nodes: Vec<GameNode>,
-    node_map: HashMap<String, usize>,
+    /// Most nodes the tree may hold, the root included
+    max_nodes: usize,
+    rules: GameRules,
+    seed: u64,
+    /// Risk profile of player 1 and player 2, in that order
+    risk_profiles: [RiskProfile; 2],
+    /// Boldness of the generated claims, overriding the risk profiles'
+    boldness_levels: Option<Vec<f64>>,
+    /// Generated claims bolder than this are bluffs
+    bluff_threshold: f64,
+    /// Claims bolder than this can only be challenged
+    accept_limit: Option<f64>,
 }
 
 impl GameTree {
+    /// Create a tree whose challenge resolutions are seeded from entropy,
+    /// so separate trees resolve the same challenge independently
     pub fn new(root_state: GameState) -> Self {
+        Self::new_with_rules(root_state, GameRules::default())
+    }
+
+    pub fn new_with_rules(root_state: GameState, rules: GameRules) -> Self {
         let root = GameNode::new(root_state);
-        let mut nodes = Vec::new();
-        nodes.push(root);
 
         Self {
-            nodes,
-            node_map: HashMap::new(),
+            nodes: vec![root],
+            max_nodes: usize::MAX,
+            rules,
+            seed: rand::random(),
+            risk_profiles: [RiskProfile::Balanced; 2],
+            boldness_levels: None,
+            bluff_threshold: DEFAULT_BLUFF_THRESHOLD,
+            accept_limit: None,
         }
     }
 
+    /// Create a tree generating one claim of each type at every level of
+    /// `boldness_levels` for both players, whatever their risk profiles,
+    /// with the claims bolder than `bluff_threshold` being bluffs. See
+    /// `ClaimLevels::new` for the levels accepted, and `set_claim_levels`
+    /// for trees with other rules or a seed.
+    pub fn with_claim_levels(
+        root_state: GameState,
+        boldness_levels: Vec<f64>,
+        bluff_threshold: f64,
+    ) -> Result<Self, InvalidClaimLevels> {
+        let mut tree = Self::new(root_state);
+        tree.set_claim_levels(ClaimLevels::new(boldness_levels, bluff_threshold)?);
+        Ok(tree)
+    }
+
+    /// Create a tree that holds at most `max_nodes` nodes, the root
+    /// included; `add_child` fails beyond that
+    pub fn with_capacity(root_state: GameState, max_nodes: usize) -> Self {
+        let mut tree = Self::new(root_state);
+        tree.max_nodes = max_nodes.max(1);
+        tree
+    }
+
+    /// Create a tree whose challenge resolutions are fully determined by `seed`
+    pub fn with_seed(root_state: GameState, seed: u64) -> Self {
+        let mut tree = Self::new(root_state);
+        tree.set_seed(seed);
+        tree
+    }
+
+    /// Reseed resolution. The same state, move and seed always resolve
+    /// the same way, regardless of search order or thread.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+
+    /// Generate `player`'s claims at the boldness levels of `profile`
+    pub fn set_risk_profile(&mut self, player: Player, profile: RiskProfile) {
+        self.risk_profiles[player.to_id() as usize - 1] = profile;
+    }
+
+    pub fn risk_profile(&self, player: Player) -> RiskProfile {
+        self.risk_profiles[player.to_id() as usize - 1]
+    }
+
+    /// Generate both players' claims at `levels` instead of at the
+    /// boldness levels of their risk profiles
+    pub fn set_claim_levels(&mut self, levels: ClaimLevels) {
+        self.boldness_levels = Some(levels.boldness_levels);
+        self.bluff_threshold = levels.bluff_threshold;
+    }
+
+    /// Stop generating accepts of claims bolder than `limit`, leaving the
+    /// challenge as the only answer to them. `None` (the default) always
+    /// offers both.
+    pub fn set_accept_limit(&mut self, limit: Option<f64>) {
+        self.accept_limit = limit;
+    }
+
+    pub fn rules(&self) -> &GameRules {
+        &self.rules
+    }
+
     pub fn root(&self) -> &GameNode {
         &self.nodes[0]
     }
@@ -63,9 +270,13 @@ impl GameTree {
         self.nodes.get_mut(index)
     }
 
-    pub fn add_child(&mut self, parent_index: usize, state: GameState, move_made: Move) -> usize {
+    pub fn add_child(&mut self, parent_index: usize, state: GameState, move_made: Move) -> Result<usize, TreeFull> {
+        if self.nodes.len() >= self.max_nodes {
+            return Err(TreeFull { max_nodes: self.max_nodes });
+        }
+
         let child_index = self.nodes.len();
-        let mut child = GameNode::with_parent(state, parent_index, move_made);
+        let child = GameNode::with_parent(state, parent_index, move_made);
         
         self.nodes.push(child);
 
@@ -73,7 +284,11 @@ impl GameTree {
             parent.children.push(child_index);
         }
 
-        child_index
+        Ok(child_index)
+    }
+
+    pub fn max_nodes(&self) -> usize {
+        self.max_nodes
     }
 
     pub fn generate_moves(&self, state: &GameState, player: Player) -> Vec<Move> {
@@ -84,13 +299,13 @@ impl GameTree {
         }
     }
 
-    fn generate_claim_moves(&self, state: &GameState, player: Player) -> Vec<Move> {
+    fn generate_claim_moves(&self, _state: &GameState, player: Player) -> Vec<Move> {
         let mut moves = Vec::new();
+        let profile_levels = self.risk_profile(player).boldness_levels();
+        let levels = self.boldness_levels.as_deref().unwrap_or(&profile_levels);
 
         // Generate claims with varying boldness levels
-        let boldness_levels = vec![0.2, 0.4, 0.6, 0.8];
-
-        for boldness in boldness_levels {
+        for &boldness in levels {
             for claim_type in [
                 crate::ClaimType::Information,
                 crate::ClaimType::Prediction,
@@ -101,14 +316,15 @@ impl GameTree {
                     description: format!("Generated claim with boldness {}", boldness),
                     claim_type,
                     boldness,
-                    is_bluff: boldness > 0.5, // Simple heuristic
+                    is_bluff: boldness > self.bluff_threshold,
                 };
 
                 moves.push(Move {
                     action: Action::MakeClaim,
                     player,
                     claim: Some(claim),
-                    confidence: 1.0 - (boldness * 0.3),
+                    confidence: Move::claim_confidence(boldness),
+                    trust_after: None,
                 });
             }
         }
@@ -116,75 +332,310 @@ impl GameTree {
         moves
     }
 
-    fn generate_challenge_moves(&self, _state: &GameState, player: Player) -> Vec<Move> {
-        vec![
-            Move {
-                action: Action::Challenge,
-                player,
-                claim: None,
-                confidence: 0.7,
-            },
-            Move {
+    fn generate_challenge_moves(&self, state: &GameState, player: Player) -> Vec<Move> {
+        let claim = state.current_claim.as_ref();
+        let mut moves = vec![Move {
+            action: Action::Challenge,
+            player,
+            claim: None,
+            confidence: Move::challenge_confidence(claim),
+            trust_after: None,
+        }];
+
+        let too_bold = matches!(
+            (claim, self.accept_limit),
+            (Some(claim), Some(limit)) if claim.boldness > limit
+        );
+        if !too_bold {
+            moves.push(Move {
                 action: Action::Accept,
                 player,
                 claim: None,
                 confidence: 0.6,
-            },
-        ]
+                trust_after: None,
+            });
+        }
+
+        moves
+    }
+
+    /// Whether `move_made` may be played in `state`: claims in the claim
+    /// phase, challenges and accepts in the challenge phase and nothing in
+    /// the resolution phase. A claim must carry a claim with boldness in
+    /// [0, 1]; the other moves must not carry one. Generated moves are
+    /// always legal.
+    pub fn is_legal(&self, state: &GameState, move_made: &Move) -> bool {
+        self.check_legal(state, move_made).is_ok()
+    }
+
+    fn check_legal(&self, state: &GameState, move_made: &Move) -> Result<(), IllegalMove> {
+        let in_phase = matches!(
+            (state.phase, move_made.action),
+            (Phase::Claim, Action::MakeClaim) | (Phase::Challenge, Action::Challenge | Action::Accept)
+        );
+        if !in_phase {
+            return Err(IllegalMove::WrongPhase {
+                phase: state.phase,
+                action: move_made.action,
+            });
+        }
+
+        let well_formed = match (&move_made.action, &move_made.claim) {
+            (Action::MakeClaim, Some(claim)) => (0.0..=1.0).contains(&claim.boldness),
+            (Action::MakeClaim, None) => false,
+            (_, claim) => claim.is_none(),
+        };
+        if !well_formed {
+            return Err(IllegalMove::MalformedClaim);
+        }
+
+        Ok(())
+    }
+
+    /// Play `move_made` on a copy of `state`, refusing moves that are not
+    /// legal there. The searches play generated moves with `make_move`.
+    pub fn apply_move(&self, state: &GameState, move_made: &Move) -> Result<GameState, IllegalMove> {
+        self.check_legal(state, move_made)?;
+
+        let mut next = state.clone();
+        self.make_move(&mut next, move_made);
+        Ok(next)
+    }
+
+    /// `apply_move` that also carries the position's Zobrist hash forward,
+    /// given `hash` for `state`, without rehashing the whole new state
+    pub fn apply_move_hashed(&self, state: &GameState, hash: u64, move_made: &Move) -> (GameState, u64) {
+        let mut next = state.clone();
+        self.make_move(&mut next, move_made);
+        let next_hash = crate::zobrist::update(hash, state, &next);
+        (next, next_hash)
+    }
+
+    /// Play `move_made` on `state` in place, resolving a challenge the way
+    /// `apply_move` does, and return what `undo_move` needs to take it back.
+    /// Saves cloning the state, move history and all, for every move.
+    pub fn make_move(&self, state: &mut GameState, move_made: &Move) -> UndoToken {
+        let (chances, count) = self.outcome_chances(state, move_made);
+
+        // Sample one outcome according to its probability
+        let roll = self.resolution_roll(state, move_made);
+        let mut cumulative = 0.0;
+        let index = chances[..count]
+            .iter()
+            .position(|probability| {
+                cumulative += probability;
+                roll < cumulative
+            })
+            .unwrap_or(count - 1);
+
+        self.make_outcome(state, move_made, index)
     }
 
-    pub fn apply_move(&self, state: &GameState, move_made: &Move) -> GameState {
-        let mut new_state = state.clone();
+    /// Play `move_made` on `state` in place with the outcome numbered
+    /// `outcome` in `outcome_probabilities`
+    pub fn make_outcome(&self, state: &mut GameState, move_made: &Move, outcome: usize) -> UndoToken {
+        let mut undo = UndoToken {
+            phase: state.phase,
+            round: state.round,
+            player1_delta: 0,
+            player2_delta: 0,
+            claim: None,
+        };
+        state.move_history.push(move_made.clone());
 
         match move_made.action {
             Action::MakeClaim => {
-                new_state.current_claim = move_made.claim.clone();
-                new_state.phase = Phase::Challenge;
+                undo.claim = std::mem::replace(&mut state.current_claim, move_made.claim.clone());
+                state.phase = Phase::Challenge;
             }
             Action::Challenge | Action::Accept => {
-                new_state.phase = Phase::Resolution;
-                // Simulate outcome based on claim boldness
-                if let Some(claim) = &new_state.current_claim {
-                    let success_prob = 0.6 - (claim.boldness * 0.3);
-                    let is_successful = rand::random::<f64>() < success_prob;
-
-                    if move_made.action == Action::Challenge {
-                        if !is_successful {
-                            // Challenge succeeded (claim was bluff)
-                            match move_made.player {
-                                Player::Player1 => new_state.player1_trust += 15,
-                                Player::Player2 => new_state.player2_trust += 15,
-                            }
-                        } else {
-                            // Challenge failed
-                            match move_made.player {
-                                Player::Player1 => new_state.player1_trust -= 15,
-                                Player::Player2 => new_state.player2_trust -= 15,
+                // Resolving the claim ends the round; the next one starts
+                // with a fresh claim
+                undo.claim = state.current_claim.take();
+                state.phase = Phase::Claim;
+                state.round = state.round.saturating_add(1);
+
+                if undo.claim.is_some() {
+                    let (player, delta) = match (move_made.action, outcome) {
+                        (Action::Accept, _) => (move_made.player.opponent(), 5),
+                        // Challenge failed (claim held up)
+                        (_, 0) => (move_made.player, -15),
+                        // Challenge succeeded (claim was bluff)
+                        (_, 1) => (move_made.player, 15),
+                        // Challenge went nowhere: the claim is withdrawn
+                        _ => {
+                            if !self.rules.inconclusive_advances_round {
+                                state.round = undo.round;
                             }
+                            (move_made.player, 0)
                         }
-                    } else {
-                        // Accepted
-                        match move_made.player.opponent() {
-                            Player::Player1 => new_state.player1_trust += 5,
-                            Player::Player2 => new_state.player2_trust += 5,
-                        }
+                    };
+                    match player {
+                        Player::Player1 => undo.player1_delta = delta,
+                        Player::Player2 => undo.player2_delta = delta,
                     }
                 }
             }
         }
 
-        // Add move to history
-        new_state.move_history.push(move_made.clone());
+        state.player1_trust += undo.player1_delta;
+        state.player2_trust += undo.player2_delta;
+        if let Some(recorded) = state.move_history.last_mut() {
+            recorded.trust_after = Some((state.player1_trust, state.player2_trust));
+        }
+        undo
+    }
+
+    /// Take back the move `undo` was returned for, which must be the last
+    /// move made on `state`, restoring it exactly
+    pub fn undo_move(&self, state: &mut GameState, undo: UndoToken) {
+        state.move_history.pop();
+        state.phase = undo.phase;
+        state.round = undo.round;
+        state.player1_trust -= undo.player1_delta;
+        state.player2_trust -= undo.player2_delta;
+        state.current_claim = undo.claim;
+    }
+
+    /// Uniform roll in [0, 1) derived from the seed, the state and the move
+    fn resolution_roll(&self, state: &GameState, move_made: &Move) -> f64 {
+        let mut hasher = StableHasher::new();
+        self.seed.hash(&mut hasher);
+        state.state_key().hash(&mut hasher);
+        crate::hash_move(move_made, &mut hasher);
+
+        // SplitMix64 finalizer to spread FNV's weak low bits
+        let mut z = hasher.finish();
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Probability of each way `move_made` can turn out in `state`, in the
+    /// order `make_outcome` numbers them. Claims and acceptances are
+    /// deterministic; a challenge against a standing claim is "upheld",
+    /// then "refuted", then "inconclusive" if the rules allow challenges
+    /// to end that way.
+    pub fn outcome_probabilities(&self, state: &GameState, move_made: &Move) -> Vec<f64> {
+        let (chances, count) = self.outcome_chances(state, move_made);
+        chances[..count].to_vec()
+    }
+
+    /// `outcome_probabilities` without allocating: the first `count` entries
+    fn outcome_chances(&self, state: &GameState, move_made: &Move) -> ([f64; 3], usize) {
+        let claim = match (&state.current_claim, move_made.action) {
+            (Some(claim), Action::Challenge) => claim,
+            _ => return ([1.0, 0.0, 0.0], 1),
+        };
 
-        new_state
+        // Simulate outcome based on claim boldness
+        let success_prob = self.rules.challenge_success_probability(claim);
+        let inconclusive_prob = self.rules.inconclusive_probability.clamp(0.0, 1.0);
+        let decisive_prob = 1.0 - inconclusive_prob;
+
+        let chances = [
+            success_prob * decisive_prob,
+            (1.0 - success_prob) * decisive_prob,
+            inconclusive_prob,
+        ];
+        (chances, if inconclusive_prob > 0.0 { 3 } else { 2 })
+    }
+
+    /// Enumerate every state a move can lead to, paired with its probability,
+    /// in the order of `outcome_probabilities`
+    pub fn resolution_outcomes(&self, state: &GameState, move_made: &Move) -> Vec<(GameState, f64)> {
+        let (chances, count) = self.outcome_chances(state, move_made);
+
+        (0..count)
+            .map(|outcome| {
+                let mut next = state.clone();
+                self.make_outcome(&mut next, move_made, outcome);
+                (next, chances[outcome])
+            })
+            .collect()
     }
 
     pub fn is_terminal(&self, state: &GameState) -> bool {
-        state.round >= 20
-            || state.player1_trust >= 100
-            || state.player2_trust >= 100
-            || state.player1_trust <= -50
-            || state.player2_trust <= -50
+        self.rules.is_terminal(state)
+    }
+
+    pub fn outcome(&self, state: &GameState) -> Option<GameOutcome> {
+        self.rules.outcome(state)
+    }
+
+    /// Whether `state`, with `player` to move, is a draw: by the rules, or
+    /// by repetition. `line` holds the Zobrist hash and player to move of
+    /// every position played through to reach `state`, starting from the
+    /// game's own history (see `history_positions`) and ending with `state`
+    /// itself; the game is drawn once one of them has come up
+    /// `REPETITION_LIMIT` times. An inconclusive challenge that keeps the
+    /// round can restore an earlier position, so lines may otherwise cycle
+    /// through claims and challenges without getting anywhere.
+    pub fn is_draw(&self, state: &GameState, player: Player, line: &[(u64, Player)]) -> bool {
+        if self.outcome(state) == Some(GameOutcome::Draw) {
+            return true;
+        }
+
+        let position = (state.zobrist_hash(), player);
+        line.iter().filter(|&&seen| seen == position).count() >= REPETITION_LIMIT
+    }
+
+    /// Zobrist hash and player to move of the position before each move in
+    /// `state.move_history`, oldest first, rebuilt by taking the moves back
+    /// from `state` with the trust recorded in `Move::trust_after`. Stops
+    /// at the most recent move that cannot be taken back, so only the
+    /// latest positions are returned when older moves lack a snapshot or
+    /// do not fit the phase they would have left behind.
+    pub fn history_positions(&self, state: &GameState) -> Vec<(u64, Player)> {
+        let history = &state.move_history;
+        let mut position = GameState {
+            move_history: Vec::new(),
+            current_claim: state.current_claim.clone(),
+            ..*state
+        };
+        let mut positions = Vec::with_capacity(history.len());
+
+        for (index, played) in history.iter().enumerate().rev() {
+            let left_behind = match played.action {
+                Action::MakeClaim => Phase::Challenge,
+                Action::Challenge | Action::Accept => Phase::Claim,
+            };
+            if position.phase != left_behind {
+                break;
+            }
+
+            let earlier = index.checked_sub(1).map(|i| &history[i]);
+            match played.action {
+                Action::MakeClaim => {
+                    position.phase = Phase::Claim;
+                    position.current_claim = None;
+                }
+                Action::Challenge | Action::Accept => {
+                    let (Some(claim), Some((player1_trust, player2_trust))) = (
+                        earlier.and_then(|m| m.claim.clone()),
+                        earlier.and_then(|m| m.trust_after),
+                    ) else {
+                        break;
+                    };
+                    // Only an inconclusive challenge leaves trust alone,
+                    // and it may have kept the round
+                    let unchanged = (player1_trust, player2_trust) == (position.player1_trust, position.player2_trust);
+                    if !unchanged || self.rules.inconclusive_advances_round {
+                        position.round = position.round.saturating_sub(1);
+                    }
+                    position.player1_trust = player1_trust;
+                    position.player2_trust = player2_trust;
+                    position.phase = Phase::Challenge;
+                    position.current_claim = Some(claim);
+                }
+            }
+            positions.push((position.zobrist_hash(), played.player));
+        }
+
+        positions.reverse();
+        positions
     }
 
     pub fn node_count(&self) -> usize {
@@ -221,4 +672,403 @@ mod tests {
         let moves = tree.generate_moves(&state, Player::Player1);
         assert!(!moves.is_empty());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_seeded_resolution_is_reproducible() {
+        let mut state = create_test_state();
+        state.phase = Phase::Challenge;
+        state.current_claim = Some(crate::Claim {
+            description: "Test claim".to_string(),
+            claim_type: crate::ClaimType::Information,
+            boldness: 0.5,
+            is_bluff: false,
+        });
+        let challenge = Move {
+            action: Action::Challenge,
+            player: Player::Player2,
+            claim: None,
+            confidence: 0.7,
+            trust_after: None,
+        };
+
+        let first = GameTree::with_seed(state.clone(), 42);
+        let second = GameTree::with_seed(state.clone(), 42);
+
+        for _ in 0..10 {
+            let a = first.apply_move(&state, &challenge).unwrap();
+            let b = second.apply_move(&state, &challenge).unwrap();
+            assert_eq!(a.player2_trust, b.player2_trust);
+        }
+
+        // Different seeds must be able to resolve the challenge differently
+        let outcomes: std::collections::HashSet<i32> = (0..64)
+            .map(|seed| GameTree::with_seed(state.clone(), seed).apply_move(&state, &challenge).unwrap().player2_trust)
+            .collect();
+        assert_eq!(outcomes.len(), 2);
+    }
+
+    #[test]
+    fn test_resolution_outcomes_probabilities() {
+        let mut state = create_test_state();
+        state.phase = Phase::Challenge;
+        state.current_claim = Some(crate::Claim {
+            description: "Test claim".to_string(),
+            claim_type: crate::ClaimType::Prediction,
+            boldness: 0.8,
+            is_bluff: true,
+        });
+        let tree = GameTree::new(state.clone());
+
+        let challenge = tree.generate_moves(&state, Player::Player2)
+            .into_iter()
+            .find(|m| m.action == Action::Challenge)
+            .unwrap();
+        let outcomes = tree.resolution_outcomes(&state, &challenge);
+
+        assert_eq!(outcomes.len(), 2);
+        let total: f64 = outcomes.iter().map(|(_, p)| p).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        assert_eq!(outcomes[0].0.player2_trust, 35);
+        assert_eq!(outcomes[1].0.player2_trust, 65);
+    }
+
+    #[test]
+    fn test_inconclusive_challenge() {
+        let mut state = create_test_state();
+        state.phase = Phase::Challenge;
+        state.current_claim = Some(crate::Claim {
+            description: "Test claim".to_string(),
+            claim_type: crate::ClaimType::Prediction,
+            boldness: 0.4,
+            is_bluff: false,
+        });
+        let rules = GameRules {
+            inconclusive_probability: 0.25,
+            ..GameRules::default()
+        };
+        let tree = GameTree::new_with_rules(state.clone(), rules);
+
+        let challenge = tree.generate_moves(&state, Player::Player2)
+            .into_iter()
+            .find(|m| m.action == Action::Challenge)
+            .unwrap();
+        let outcomes = tree.resolution_outcomes(&state, &challenge);
+
+        assert_eq!(outcomes.len(), 3);
+        let total: f64 = outcomes.iter().map(|(_, p)| p).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+
+        let (inconclusive, probability) = &outcomes[2];
+        assert!((probability - 0.25).abs() < 1e-9);
+        assert_eq!(inconclusive.player1_trust, state.player1_trust);
+        assert_eq!(inconclusive.player2_trust, state.player2_trust);
+        assert_eq!(inconclusive.phase, Phase::Claim);
+        assert!(inconclusive.current_claim.is_none());
+        assert_eq!(inconclusive.round, state.round);
+        assert_eq!(outcomes[0].0.round, state.round + 1);
+
+        // Sampled resolution hits it about as often as configured
+        let hits = (0..2000)
+            .filter(|&seed| {
+                let mut tree = GameTree::new_with_rules(state.clone(), rules);
+                tree.set_seed(seed);
+                let resolved = tree.apply_move(&state, &challenge).unwrap();
+                resolved.player2_trust == state.player2_trust
+            })
+            .count();
+        assert!((hits as f64 / 2000.0 - 0.25).abs() < 0.05, "{hits} inconclusive of 2000");
+    }
+
+    #[test]
+    fn test_rounds_advance_to_the_round_cap() {
+        // Trust thresholds out of reach, so only the round cap can end the game
+        let rules = GameRules { win_trust: 1000, ..GameRules::default() };
+        let tree = GameTree::new_with_rules(create_test_state(), rules);
+        let mut state = create_test_state();
+        let mut rounds = 0;
+
+        while !tree.is_terminal(&state) {
+            let claim = tree.generate_moves(&state, Player::Player1).swap_remove(0);
+            state = tree.apply_move(&state, &claim).unwrap();
+            assert_eq!(state.phase, Phase::Challenge);
+
+            let accept = tree.generate_moves(&state, Player::Player2)
+                .into_iter()
+                .find(|m| m.action == Action::Accept)
+                .unwrap();
+            state = tree.apply_move(&state, &accept).unwrap();
+            assert_eq!(state.phase, Phase::Claim);
+            assert!(state.current_claim.is_none());
+
+            rounds += 1;
+            assert!(rounds < 100, "the round counter never reached the cap");
+        }
+
+        assert_eq!(state.round, tree.rules().max_round);
+    }
+
+    #[test]
+    fn test_undo_restores_state_exactly() {
+        let rules = GameRules {
+            inconclusive_probability: 0.2,
+            ..GameRules::default()
+        };
+        let tree = GameTree::new_with_rules(create_test_state(), rules);
+        let json = |state: &GameState| serde_json::to_string(state).unwrap();
+        let mut state = create_test_state();
+        let original = json(&state);
+
+        let claim = tree.generate_moves(&state, Player::Player1).swap_remove(7);
+        let undo = tree.make_move(&mut state, &claim);
+        tree.undo_move(&mut state, undo);
+        assert_eq!(json(&state), original);
+
+        tree.make_move(&mut state, &claim);
+        let claimed = state.clone();
+        for reply in tree.generate_moves(&claimed, Player::Player2) {
+            for outcome in 0..tree.outcome_probabilities(&claimed, &reply).len() {
+                let undo = tree.make_outcome(&mut state, &reply, outcome);
+                assert_ne!(json(&state), json(&claimed));
+                tree.undo_move(&mut state, undo);
+                assert_eq!(json(&state), json(&claimed));
+            }
+
+            // Made in place, a move lands where `apply_move` does
+            let undo = tree.make_move(&mut state, &reply);
+            assert_eq!(json(&state), json(&tree.apply_move(&claimed, &reply).unwrap()));
+            tree.undo_move(&mut state, undo);
+        }
+    }
+
+    #[test]
+    fn test_threefold_repetition_is_a_draw() {
+        // Every challenge is inconclusive and keeps the round, so a claim
+        // and its challenge lead back to where they started
+        let rules = GameRules {
+            inconclusive_probability: 1.0,
+            ..GameRules::default()
+        };
+        let tree = GameTree::new_with_rules(create_test_state(), rules);
+        let mut state = create_test_state();
+        let mut line = vec![(state.zobrist_hash(), Player::Player1)];
+
+        for repetition in 1..REPETITION_LIMIT {
+            assert!(!tree.is_draw(&state, Player::Player1, &line));
+
+            let claim = tree.generate_moves(&state, Player::Player1).swap_remove(0);
+            state = tree.apply_move(&state, &claim).unwrap();
+            line.push((state.zobrist_hash(), Player::Player2));
+
+            let challenge = tree.generate_moves(&state, Player::Player2)
+                .into_iter()
+                .find(|m| m.action == Action::Challenge)
+                .unwrap();
+            state = tree.apply_move(&state, &challenge).unwrap();
+            line.push((state.zobrist_hash(), Player::Player1));
+
+            assert_eq!(state.move_history.len(), 2 * repetition);
+        }
+
+        assert!(tree.is_draw(&state, Player::Player1, &line));
+        // The same position with the other player to move is another position
+        assert!(!tree.is_draw(&state, Player::Player2, &line));
+    }
+
+    #[test]
+    fn test_history_positions_match_the_positions_played() {
+        for rules in [GameRules::default(), GameRules { inconclusive_probability: 0.5, ..GameRules::default() }] {
+            let tree = GameTree::new_with_rules(create_test_state(), rules);
+            let mut state = create_test_state();
+            let mut played = Vec::new();
+            let mut player = Player::Player1;
+
+            for turn in 0..12 {
+                let moves = tree.generate_moves(&state, player);
+                if moves.is_empty() {
+                    break;
+                }
+                played.push((state.zobrist_hash(), player));
+                tree.make_move(&mut state, &moves[turn % moves.len()]);
+                player = player.opponent();
+            }
+
+            assert_eq!(tree.history_positions(&state), played);
+        }
+
+        // Without a snapshot, nothing before the challenge can be rebuilt
+        let tree = GameTree::new(create_test_state());
+        let mut state = create_test_state();
+        let claim = tree.generate_moves(&state, Player::Player1).swap_remove(0);
+        tree.make_move(&mut state, &claim);
+        let challenge = tree.generate_moves(&state, Player::Player2).swap_remove(0);
+        tree.make_move(&mut state, &challenge);
+        state.move_history[0].trust_after = None;
+        assert!(tree.history_positions(&state).is_empty());
+    }
+
+    #[test]
+    fn test_add_child_stops_at_capacity() {
+        let state = create_test_state();
+        let mut tree = GameTree::with_capacity(state.clone(), 2);
+        let moves = tree.generate_moves(&state, Player::Player1);
+
+        let child = tree.add_child(0, state.clone(), moves[0].clone()).unwrap();
+        assert_eq!(child, 1);
+        assert_eq!(
+            tree.add_child(0, state.clone(), moves[1].clone()),
+            Err(TreeFull { max_nodes: 2 })
+        );
+
+        // The rejected child left no trace
+        assert_eq!(tree.node_count(), 2);
+        assert_eq!(tree.root().children, vec![1]);
+        assert_eq!(tree.get_node(1).unwrap().parent, Some(0));
+    }
+
+    #[test]
+    fn test_single_boldness_level_gives_one_claim_per_type() {
+        let state = create_test_state();
+        let tree = GameTree::with_claim_levels(state.clone(), vec![0.7], 0.6).unwrap();
+
+        let moves = tree.generate_moves(&state, Player::Player1);
+        assert_eq!(moves.len(), 4);
+        assert!(moves.iter().all(|m| {
+            let claim = m.claim.as_ref().unwrap();
+            claim.boldness == 0.7 && claim.is_bluff
+        }));
+
+        assert_eq!(
+            GameTree::with_claim_levels(state.clone(), vec![0.2, 1.5], 0.5).err(),
+            Some(InvalidClaimLevels::OutOfRange(1.5))
+        );
+        assert_eq!(GameTree::with_claim_levels(state, Vec::new(), 0.5).err(), Some(InvalidClaimLevels::Empty));
+    }
+
+    #[test]
+    fn test_claim_levels_must_hash_apart() {
+        assert_eq!(ClaimLevels::new(vec![0.3, 0.35], 0.5), Err(InvalidClaimLevels::OffGrid(0.35)));
+        // Only the levels are hashed, so the threshold may lie anywhere
+        assert!(ClaimLevels::new(vec![0.1, 0.3, 0.7], 0.45).is_ok());
+    }
+
+    #[test]
+    fn test_claim_levels_keep_rules_and_seed() {
+        let rules = GameRules {
+            win_trust: 60,
+            ..GameRules::default()
+        };
+        let state = create_test_state();
+        let mut tree = GameTree::new_with_rules(state.clone(), rules);
+        tree.set_seed(3);
+        tree.set_claim_levels(ClaimLevels::new(vec![0.3, 0.9], 0.5).unwrap());
+
+        assert_eq!(tree.rules().win_trust, 60);
+        let levels: Vec<f64> = tree
+            .generate_moves(&state, Player::Player2)
+            .iter()
+            .map(|m| m.claim.as_ref().unwrap().boldness)
+            .collect();
+        assert_eq!(levels, [[0.3; 4], [0.9; 4]].concat());
+
+        // Resolution still follows the seed
+        let seeded = GameTree::with_seed(state.clone(), 3);
+        let claim = seeded.generate_moves(&state, Player::Player1).swap_remove(0);
+        let claimed = seeded.apply_move(&state, &claim).unwrap();
+        let challenge = seeded.generate_moves(&claimed, Player::Player2).swap_remove(0);
+        assert_eq!(
+            tree.apply_move(&claimed, &challenge).unwrap().player2_trust,
+            seeded.apply_move(&claimed, &challenge).unwrap().player2_trust
+        );
+    }
+
+    #[test]
+    fn test_challenge_confidence_follows_claim() {
+        let challenge_confidence = |boldness: f64, is_bluff: bool| {
+            let mut state = create_test_state();
+            state.phase = Phase::Challenge;
+            state.current_claim = Some(Claim {
+                description: "claim".to_string(),
+                claim_type: crate::ClaimType::Prediction,
+                boldness,
+                is_bluff,
+            });
+            let moves = GameTree::new(state.clone()).generate_moves(&state, Player::Player2);
+            assert_eq!(moves[0].action, Action::Challenge);
+            moves[0].confidence
+        };
+
+        assert!(challenge_confidence(0.9, false) > challenge_confidence(0.2, false));
+        assert!(challenge_confidence(0.6, true) > challenge_confidence(0.6, false));
+    }
+
+    #[test]
+    fn test_accept_limit_leaves_only_challenge() {
+        let mut state = create_test_state();
+        state.phase = Phase::Challenge;
+        state.current_claim = Some(Claim {
+            description: "claim".to_string(),
+            claim_type: crate::ClaimType::Accusation,
+            boldness: 0.95,
+            is_bluff: true,
+        });
+        let mut tree = GameTree::new(state.clone());
+        assert_eq!(tree.generate_moves(&state, Player::Player2).len(), 2);
+
+        tree.set_accept_limit(Some(0.9));
+        let moves = tree.generate_moves(&state, Player::Player2);
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0].action, Action::Challenge);
+    }
+
+    #[test]
+    fn test_out_of_phase_actions_are_illegal() {
+        let claim = |tree: &GameTree, state: &GameState| tree.generate_moves(state, Player::Player1).remove(0);
+        let reply = |action| Move {
+            action,
+            player: Player::Player2,
+            claim: None,
+            confidence: 0.5,
+            trust_after: None,
+        };
+
+        // Claim phase: only claims
+        let state = create_test_state();
+        let tree = GameTree::new(state.clone());
+        let claim_move = claim(&tree, &state);
+        assert!(tree.is_legal(&state, &claim_move));
+        for action in [Action::Challenge, Action::Accept] {
+            assert!(!tree.is_legal(&state, &reply(action)));
+            assert_eq!(
+                tree.apply_move(&state, &reply(action)).err(),
+                Some(IllegalMove::WrongPhase { phase: Phase::Claim, action })
+            );
+        }
+
+        // Challenge phase: only challenges and accepts
+        let claimed = tree.apply_move(&state, &claim_move).unwrap();
+        assert!(!tree.is_legal(&claimed, &claim(&tree, &state)));
+        assert!(tree.is_legal(&claimed, &reply(Action::Challenge)));
+        assert!(tree.is_legal(&claimed, &reply(Action::Accept)));
+
+        // Resolution phase: nothing
+        let mut resolving = claimed.clone();
+        resolving.phase = Phase::Resolution;
+        assert!(!tree.is_legal(&resolving, &claim_move));
+        for action in [Action::Challenge, Action::Accept] {
+            assert!(!tree.is_legal(&resolving, &reply(action)));
+        }
+    }
+
+    #[test]
+    fn test_malformed_claims_are_illegal() {
+        let state = create_test_state();
+        let tree = GameTree::new(state.clone());
+        let mut claim_move = tree.generate_moves(&state, Player::Player1).remove(0);
+
+        claim_move.claim.as_mut().unwrap().boldness = 1.5;
+        assert_eq!(tree.apply_move(&state, &claim_move).err(), Some(IllegalMove::MalformedClaim));
+
+        claim_move.claim = None;
+        assert!(!tree.is_legal(&state, &claim_move));
+    }
+}
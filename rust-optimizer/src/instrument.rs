@@ -0,0 +1,124 @@
+//! Search and FFI diagnostics. With the `tracing` feature they are
+//! `tracing` spans and events, so whatever subscriber the host installs
+//! picks them up: every alpha-beta search runs in a `search` span under
+//! the `SEARCH_TARGET` target, with an event for each depth iteration it
+//! completes and one when it finishes, and FFI warnings and errors are
+//! events under `FFI_TARGET`. Without the feature, searches record
+//! nothing and FFI messages go to stderr.
+
+use crate::Player;
+
+pub const SEARCH_TARGET: &str = "strategic_mind_optimizer::search";
+pub const FFI_TARGET: &str = "strategic_mind_optimizer::ffi";
+
+/// The `search` span, entered until this is dropped at the end of the search
+pub(crate) struct SearchSpan {
+    #[cfg(feature = "tracing")]
+    _entered: tracing::span::EnteredSpan,
+}
+
+pub(crate) fn search_started(player: Player, max_depth: u8) -> SearchSpan {
+    #[cfg(feature = "tracing")]
+    return SearchSpan {
+        _entered: tracing::debug_span!(target: SEARCH_TARGET, "search", player = player.to_id(), max_depth).entered(),
+    };
+    #[cfg(not(feature = "tracing"))]
+    {
+        let _ = (player, max_depth);
+        SearchSpan {}
+    }
+}
+
+/// A depth iteration, or the single depth of a fixed-depth search, ran to
+/// the end
+pub(crate) fn depth_completed(depth: u8, nodes: u64, best_eval: f64, tt_hits: u64, null_move_cutoffs: u64) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        target: SEARCH_TARGET,
+        depth,
+        nodes,
+        best_eval,
+        tt_hits,
+        null_move_cutoffs,
+        "depth completed"
+    );
+    #[cfg(not(feature = "tracing"))]
+    let _ = (depth, nodes, best_eval, tt_hits, null_move_cutoffs);
+}
+
+pub(crate) fn search_finished(depth_reached: u8, nodes: u64, best_eval: f64, time_ms: u64) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(target: SEARCH_TARGET, depth_reached, nodes, best_eval, time_ms, "search finished");
+    #[cfg(not(feature = "tracing"))]
+    let _ = (depth_reached, nodes, best_eval, time_ms);
+}
+
+/// `source` names the binding, e.g. "FFI" or "JNI"
+pub(crate) fn ffi_warning(source: &str, message: impl std::fmt::Display) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(target: FFI_TARGET, source, "{}", message);
+    #[cfg(not(feature = "tracing"))]
+    eprintln!("[{}] Warning: {}", source, message);
+}
+
+pub(crate) fn ffi_error(source: &str, message: impl std::fmt::Display) {
+    #[cfg(feature = "tracing")]
+    tracing::error!(target: FFI_TARGET, source, "{}", message);
+    #[cfg(not(feature = "tracing"))]
+    eprintln!("[{}] Error: {}", source, message);
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use super::*;
+    use crate::{AlphaBetaSearch, GameState, Phase};
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    /// Collects what a subscriber writes
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Capture {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn create_test_state() -> GameState {
+        GameState {
+            round: 1,
+            phase: Phase::Claim,
+            player1_trust: 50,
+            player2_trust: 50,
+            current_claim: None,
+            move_history: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_subscriber_captures_search_records() {
+        let capture = Capture::default();
+        let writer = capture.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::DEBUG)
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+
+        tracing::subscriber::with_default(subscriber, || {
+            AlphaBetaSearch::new(2, false).with_seed(1).search(&create_test_state(), Player::Player1);
+        });
+
+        let output = String::from_utf8(capture.0.lock().unwrap().clone()).unwrap();
+        let records: Vec<&str> = output.lines().filter(|line| line.contains(SEARCH_TARGET)).collect();
+        assert!(records.iter().all(|r| r.contains("search{player=1 max_depth=2}")));
+        assert!(records.iter().any(|r| r.contains("depth completed depth=2")));
+        assert!(records.iter().any(|r| r.contains("search finished depth_reached=2")));
+    }
+}
@@ -4,17 +4,86 @@
 //! using minimax with alpha-beta pruning.
 
 pub mod game_tree;
+pub mod rules;
 pub mod minimax;
 pub mod alpha_beta;
+pub mod mcts;
 pub mod evaluation;
+pub mod cache;
+pub mod opponent;
+pub mod profile;
+pub mod opening_book;
+pub mod equilibrium;
+pub mod analysis;
+pub mod simulation;
+pub mod tuning;
+pub mod tournament;
+pub mod zobrist;
+pub mod instrument;
 pub mod ffi;
+#[cfg(feature = "python")]
+pub mod python;
 
-pub use game_tree::{GameNode, GameTree};
+pub use game_tree::{ClaimLevels, GameNode, GameTree, IllegalMove, InvalidClaimLevels, TreeFull, UndoToken, REPETITION_LIMIT};
+pub use rules::{GameOutcome, GameRules, NoMovePolicy, MATE_SCORE};
 pub use minimax::MinimaxSearch;
-pub use alpha_beta::AlphaBetaSearch;
-pub use evaluation::Evaluator;
+pub use alpha_beta::{AlphaBetaSearch, DepthPolicy};
+pub use mcts::{MctsSearch, RolloutPolicy};
+pub use evaluation::{EvaluationBreakdown, EvaluationWeights, EvaluationWeightsBuilder, Evaluator, GameStateRange, StateEvaluator, UnknownFields, WeightSchedule};
+pub use cache::{CacheStats, LruCache};
+pub use opponent::OpponentModel;
+pub use profile::{BoldnessProfile, RiskProfile};
+pub use opening_book::{BookKey, OpeningBook};
+pub use analysis::CachedAnalysis;
+pub use simulation::{GameRecord, play_game_with_random_opening, simulate_game};
+pub use tuning::{MatchResult, self_play};
+pub use tournament::{EngineConfig, Standing, Standings};
 
 use serde::{Deserialize, Serialize};
+use std::hash::{Hash, Hasher};
+
+// The standard clock panics in the browser, so wasm32 reads the browser's
+// clock through `web_time`
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use std::time;
+#[cfg(target_arch = "wasm32")]
+pub(crate) use web_time as time;
+
+/// Largest trust, either way, a valid state may hold. Far beyond any
+/// threshold the rules use, it keeps trust arithmetic clear of overflow.
+pub const MAX_TRUST: i32 = 10_000;
+
+/// Why `GameState::validate` rejected a state
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InvalidState {
+    /// A player's trust beyond `MAX_TRUST` either way
+    TrustOutOfRange { player: Player, trust: i32 },
+    /// A claim, standing or in the history, with boldness outside [0, 1]
+    BoldnessOutOfRange(f64),
+    /// A move in the history with confidence outside [0, 1]
+    ConfidenceOutOfRange(f64),
+}
+
+impl std::fmt::Display for InvalidState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvalidState::TrustOutOfRange { player, trust } => write!(
+                f,
+                "player {} trust {} is outside [-{}, {}]",
+                player.to_id(),
+                trust,
+                MAX_TRUST,
+                MAX_TRUST
+            ),
+            InvalidState::BoldnessOutOfRange(boldness) => write!(f, "claim boldness {} is outside [0, 1]", boldness),
+            InvalidState::ConfidenceOutOfRange(confidence) => {
+                write!(f, "move confidence {} is outside [0, 1]", confidence)
+            }
+        }
+    }
+}
+
+impl std::error::Error for InvalidState {}
 
 /// Represents a game state that can be evaluated
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,14 +96,298 @@ pub struct GameState {
     pub move_history: Vec<Move>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+impl GameState {
+    /// Stable key identifying this state, history included.
+    /// Boldness and confidence are quantized to thousandths and claim
+    /// descriptions are ignored, since neither affects play.
+    pub fn state_key(&self) -> u64 {
+        let mut hasher = StableHasher::new();
+
+        self.round.hash(&mut hasher);
+        self.phase.hash(&mut hasher);
+        self.player1_trust.hash(&mut hasher);
+        self.player2_trust.hash(&mut hasher);
+        hash_claim(self.current_claim.as_ref(), &mut hasher);
+
+        (self.move_history.len() as u64).hash(&mut hasher);
+        for m in &self.move_history {
+            hash_move(m, &mut hasher);
+            m.trust_after.hash(&mut hasher);
+        }
+
+        hasher.finish()
+    }
+
+    pub fn builder() -> GameStateBuilder {
+        GameStateBuilder::default()
+    }
+
+    /// Canonical key of this state, history included, for maps and sets
+    pub fn key(&self) -> StateKey {
+        StateKey::from(self)
+    }
+
+    /// Stable key for the position alone: like `state_key`, but without the
+    /// move history, so the same position reached by different move orders
+    /// gets the same key
+    pub fn position_key(&self) -> u64 {
+        let mut hasher = StableHasher::new();
+
+        self.round.hash(&mut hasher);
+        self.phase.hash(&mut hasher);
+        self.player1_trust.hash(&mut hasher);
+        self.player2_trust.hash(&mut hasher);
+        hash_claim(self.current_claim.as_ref(), &mut hasher);
+
+        hasher.finish()
+    }
+
+    /// Zobrist hash of the position, ignoring the move history. Cheap to
+    /// keep up to date move by move with `zobrist::update` or
+    /// `GameTree::apply_move_hashed`.
+    pub fn zobrist_hash(&self) -> u64 {
+        zobrist::hash(self)
+    }
+
+    /// Check that trust lies within `MAX_TRUST` and that every boldness
+    /// and confidence lies in [0, 1]. Deserializing accepts any number, so
+    /// states from outside the library should be checked before searching.
+    pub fn validate(&self) -> Result<(), InvalidState> {
+        for (player, trust) in [(Player::Player1, self.player1_trust), (Player::Player2, self.player2_trust)] {
+            if !(-MAX_TRUST..=MAX_TRUST).contains(&trust) {
+                return Err(InvalidState::TrustOutOfRange { player, trust });
+            }
+        }
+
+        let unit = 0.0..=1.0;
+        let claims = self
+            .current_claim
+            .iter()
+            .chain(self.move_history.iter().filter_map(|m| m.claim.as_ref()));
+        for claim in claims {
+            if !unit.contains(&claim.boldness) {
+                return Err(InvalidState::BoldnessOutOfRange(claim.boldness));
+            }
+        }
+
+        match self.move_history.iter().find(|m| !unit.contains(&m.confidence)) {
+            Some(m) => Err(InvalidState::ConfidenceOutOfRange(m.confidence)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Fluent construction of a `GameState`, starting from the opening
+/// position: round 1, claim phase, trust 50 each, no standing claim and
+/// an empty history.
+///
+/// ```
+/// use strategic_mind_optimizer::{Claim, ClaimType, GameState, Phase};
+///
+/// let state = GameState::builder()
+///     .round(6)
+///     .phase(Phase::Challenge)
+///     .player1_trust(65)
+///     .player2_trust(40)
+///     .current_claim(Claim {
+///         description: "I know where the ledger is".to_string(),
+///         claim_type: ClaimType::Information,
+///         boldness: 0.7,
+///         is_bluff: true,
+///     })
+///     .build();
+///
+/// assert_eq!(state.round, 6);
+/// assert!(state.current_claim.is_some());
+/// assert!(state.move_history.is_empty());
+/// assert_eq!(state.validate(), Ok(()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct GameStateBuilder {
+    state: GameState,
+}
+
+impl Default for GameStateBuilder {
+    fn default() -> Self {
+        Self {
+            state: GameState {
+                round: 1,
+                phase: Phase::Claim,
+                player1_trust: 50,
+                player2_trust: 50,
+                current_claim: None,
+                move_history: Vec::new(),
+            },
+        }
+    }
+}
+
+impl GameStateBuilder {
+    pub fn round(mut self, round: u8) -> Self {
+        self.state.round = round;
+        self
+    }
+
+    pub fn phase(mut self, phase: Phase) -> Self {
+        self.state.phase = phase;
+        self
+    }
+
+    pub fn player1_trust(mut self, trust: i32) -> Self {
+        self.state.player1_trust = trust;
+        self
+    }
+
+    pub fn player2_trust(mut self, trust: i32) -> Self {
+        self.state.player2_trust = trust;
+        self
+    }
+
+    /// Claim awaiting its challenge or accept
+    pub fn current_claim(mut self, claim: Claim) -> Self {
+        self.state.current_claim = Some(claim);
+        self
+    }
+
+    pub fn move_history(mut self, history: Vec<Move>) -> Self {
+        self.state.move_history = history;
+        self
+    }
+
+    pub fn build(self) -> GameState {
+        self.state
+    }
+}
+
+/// FNV-1a hasher whose output does not depend on the Rust release,
+/// so keys and seeded outcomes stay reproducible across toolchains
+pub(crate) struct StableHasher(u64);
+
+impl StableHasher {
+    pub(crate) fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+}
+
+pub(crate) fn hash_move(m: &Move, hasher: &mut StableHasher) {
+    m.action.hash(hasher);
+    m.player.hash(hasher);
+    hash_claim(m.claim.as_ref(), hasher);
+    quantize(m.confidence).hash(hasher);
+}
+
+fn hash_claim(claim: Option<&Claim>, hasher: &mut StableHasher) {
+    match claim {
+        Some(claim) => {
+            1u8.hash(hasher);
+            claim.claim_type.hash(hasher);
+            quantize(claim.boldness).hash(hasher);
+            claim.is_bluff.hash(hasher);
+        }
+        None => 0u8.hash(hasher),
+    }
+}
+
+/// Boldness or confidence rounded to the nearest thousandth, in thousandths
+fn quantize(value: f64) -> i64 {
+    (value * 1000.0).round() as i64
+}
+
+/// Canonical form of a `GameState` that is `Eq` and `Hash`, for keying
+/// transposition and repetition tables. `GameState` itself holds floats,
+/// so it is neither.
+///
+/// Boldness and confidence are quantized to thousandths, rounding to the
+/// nearest, so states whose values round to the same thousandths share a
+/// key: boldness 0.5 and 0.5004 collide, 0.5 and 0.501 do not. Claim
+/// descriptions are ignored. This is the same canonical form
+/// `GameState::state_key` hashes to a `u64`, without the chance of two
+/// distinct states colliding.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StateKey(CanonicalState);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CanonicalState {
+    round: u8,
+    phase: Phase,
+    player1_trust: i32,
+    player2_trust: i32,
+    current_claim: Option<CanonicalClaim>,
+    move_history: Vec<CanonicalMove>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct CanonicalClaim {
+    claim_type: ClaimType,
+    boldness: i64,
+    is_bluff: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct CanonicalMove {
+    action: Action,
+    player: Player,
+    claim: Option<CanonicalClaim>,
+    confidence: i64,
+    trust_after: Option<(i32, i32)>,
+}
+
+impl From<&Claim> for CanonicalClaim {
+    fn from(claim: &Claim) -> Self {
+        Self {
+            claim_type: claim.claim_type,
+            boldness: quantize(claim.boldness),
+            is_bluff: claim.is_bluff,
+        }
+    }
+}
+
+impl From<&Move> for CanonicalMove {
+    fn from(m: &Move) -> Self {
+        Self {
+            action: m.action,
+            player: m.player,
+            claim: m.claim.as_ref().map(CanonicalClaim::from),
+            confidence: quantize(m.confidence),
+            trust_after: m.trust_after,
+        }
+    }
+}
+
+impl From<&GameState> for StateKey {
+    fn from(state: &GameState) -> Self {
+        StateKey(CanonicalState {
+            round: state.round,
+            phase: state.phase,
+            player1_trust: state.player1_trust,
+            player2_trust: state.player2_trust,
+            current_claim: state.current_claim.as_ref().map(CanonicalClaim::from),
+            move_history: state.move_history.iter().map(CanonicalMove::from).collect(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Phase {
     Claim,
     Challenge,
     Resolution,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Claim {
     pub description: String,
     pub claim_type: ClaimType,
@@ -42,7 +395,7 @@ pub struct Claim {
     pub is_bluff: bool,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ClaimType {
     Information,
     Prediction,
@@ -50,22 +403,61 @@ pub enum ClaimType {
     Alliance,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Move {
     pub action: Action,
     pub player: Player,
     pub claim: Option<Claim>,
     pub confidence: f64,
+    /// Player 1's and player 2's trust once the move was played, recorded
+    /// when the move enters a state's history; `None` on generated moves
+    /// and on histories that did not record it
+    #[serde(default)]
+    pub trust_after: Option<(i32, i32)>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+impl Move {
+    /// Confidence the move generator attaches to a claim of this boldness
+    pub fn claim_confidence(boldness: f64) -> f64 {
+        1.0 - boldness * 0.3
+    }
+
+    /// Confidence the move generator attaches to a challenge of `claim`:
+    /// higher the bolder the claim, and higher still for a bluff. 0.7 when
+    /// there is no claim to go by.
+    pub fn challenge_confidence(claim: Option<&Claim>) -> f64 {
+        match claim {
+            Some(claim) => {
+                let bluff_bonus = if claim.is_bluff { 0.05 } else { 0.0 };
+                (0.6 + claim.boldness * 0.2 + bluff_bonus).min(1.0)
+            }
+            None => 0.7,
+        }
+    }
+
+    /// How well this move's confidence agrees with its claim's boldness under
+    /// the generation model: 1.0 for an exact match, falling to 0.0 once they
+    /// are 0.3 apart (the model's full confidence range). Moves without a
+    /// claim have nothing to disagree with and score 1.0.
+    pub fn consistency_score(&self) -> f64 {
+        match &self.claim {
+            Some(claim) => {
+                let deviation = (self.confidence - Move::claim_confidence(claim.boldness)).abs();
+                1.0 - (deviation / 0.3).min(1.0)
+            }
+            None => 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Action {
     MakeClaim,
     Challenge,
     Accept,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Player {
     Player1,
     Player2,
@@ -78,6 +470,23 @@ impl Player {
             Player::Player2 => Player::Player1,
         }
     }
+
+    /// Numeric id used across the FFI boundary: 1 or 2
+    pub fn to_id(&self) -> u8 {
+        match self {
+            Player::Player1 => 1,
+            Player::Player2 => 2,
+        }
+    }
+
+    /// Inverse of `to_id`; any other id is rejected
+    pub fn from_id(id: u8) -> Option<Player> {
+        match id {
+            1 => Some(Player::Player1),
+            2 => Some(Player::Player2),
+            _ => None,
+        }
+    }
 }
 
 /// Result of a search operation
@@ -88,8 +497,49 @@ pub struct SearchResult {
     pub nodes_explored: u64,
     pub depth_reached: u8,
     pub time_ms: u64,
+    /// Transposition table probes that were deep enough to use
+    #[serde(default)]
+    pub tt_hits: u64,
+    /// Subtrees pruned because passing already did well enough
+    #[serde(default)]
+    pub null_move_cutoffs: u64,
+    /// Expected line of play from the root, starting with `best_move`
+    #[serde(default)]
+    pub principal_variation: Vec<Move>,
+}
+
+impl SearchResult {
+    /// How `other` differs from this result, e.g. for two engine
+    /// configurations searching the same position. Deltas are `other`
+    /// minus `self`.
+    pub fn compare(&self, other: &SearchResult) -> SearchComparison {
+        SearchComparison {
+            agreement: self.best_move == other.best_move,
+            evaluation_delta: other.evaluation - self.evaluation,
+            nodes_delta: other.nodes_explored as i64 - self.nodes_explored as i64,
+            depth_delta: other.depth_reached as i16 - self.depth_reached as i16,
+            time_delta_ms: other.time_ms as i64 - self.time_ms as i64,
+        }
+    }
 }
 
+/// Differences between two search results, from `SearchResult::compare`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchComparison {
+    /// Whether both picked the same best move
+    pub agreement: bool,
+    pub evaluation_delta: f64,
+    pub nodes_delta: i64,
+    pub depth_delta: i16,
+    pub time_delta_ms: i64,
+}
+
+// Re-export FFI functions for easier access
+pub use ffi::{search_optimal_move, search_optimal_move_pretty, search_optimal_move_seeded, search_optimal_move_minimax, search_with_risk_profile, search_with_weights, search_optimal_move_bincode, free_result_string, free_result_bytes, evaluate_state, evaluate_states_batch, get_legal_moves, configure_threads, get_last_error, initialize_optimizer, set_max_description_length, set_move_consistency_warnings};
+
+#[cfg(feature = "jni")]
+pub use ffi::jni_bindings;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -99,13 +549,169 @@ mod tests {
         assert_eq!(Player::Player1.opponent(), Player::Player2);
         assert_eq!(Player::Player2.opponent(), Player::Player1);
     }
-}
 
-// Export FFI module
-pub mod ffi;
+    #[test]
+    fn test_player_id_round_trip() {
+        for player in [Player::Player1, Player::Player2] {
+            assert_eq!(Player::from_id(player.to_id()), Some(player));
+        }
 
-// Re-export FFI functions for easier access
-pub use ffi::{search_optimal_move, free_result_string, evaluate_state, initialize_optimizer};
+        assert_eq!(Player::from_id(0), None);
+        assert_eq!(Player::from_id(3), None);
+        assert_eq!(Player::from_id(255), None);
+    }
 
-#[cfg(feature = "jni")]
-pub use ffi::jni_bindings;
\ No newline at end of file
+    #[test]
+    fn test_consistency_score() {
+        let claim_move = |boldness: f64, confidence: f64| Move {
+            action: Action::MakeClaim,
+            player: Player::Player1,
+            claim: Some(Claim {
+                description: String::new(),
+                claim_type: ClaimType::Prediction,
+                boldness,
+                is_bluff: boldness > 0.5,
+            }),
+            confidence,
+            trust_after: None,
+        };
+
+        let consistent = claim_move(0.6, Move::claim_confidence(0.6));
+        assert!((consistent.consistency_score() - 1.0).abs() < 1e-9);
+
+        let inconsistent = claim_move(0.9, 1.0);
+        assert!(inconsistent.consistency_score() < 0.2);
+    }
+
+    #[test]
+    fn test_compare_search_results() {
+        let result = |evaluation: f64, nodes_explored: u64| SearchResult {
+            best_move: Move {
+                action: Action::Challenge,
+                player: Player::Player2,
+                claim: None,
+                confidence: 0.7,
+                trust_after: None,
+            },
+            evaluation,
+            nodes_explored,
+            depth_reached: 4,
+            time_ms: 10,
+            tt_hits: 0,
+            null_move_cutoffs: 0,
+            principal_variation: Vec::new(),
+        };
+
+        let comparison = result(12.5, 300).compare(&result(8.0, 120));
+
+        assert!(comparison.agreement);
+        assert_eq!(comparison.evaluation_delta, -4.5);
+        assert_eq!(comparison.nodes_delta, -180);
+        assert_eq!(comparison.depth_delta, 0);
+
+        let mut other = result(12.5, 300);
+        other.best_move.action = Action::Accept;
+        assert!(!result(12.5, 300).compare(&other).agreement);
+    }
+
+    #[test]
+    fn test_position_key_ignores_move_order() {
+        let claim = |claim_type: ClaimType| Move {
+            action: Action::MakeClaim,
+            player: Player::Player1,
+            claim: Some(Claim {
+                description: String::new(),
+                claim_type,
+                boldness: 0.2,
+                is_bluff: false,
+            }),
+            confidence: 0.94,
+            trust_after: None,
+        };
+        let state = |history: Vec<Move>| GameState {
+            round: 2,
+            phase: Phase::Claim,
+            player1_trust: 55,
+            player2_trust: 50,
+            current_claim: None,
+            move_history: history,
+        };
+
+        let first = state(vec![claim(ClaimType::Information), claim(ClaimType::Alliance)]);
+        let second = state(vec![claim(ClaimType::Alliance), claim(ClaimType::Information)]);
+
+        assert_ne!(first.state_key(), second.state_key());
+        assert_eq!(first.position_key(), second.position_key());
+    }
+
+    #[test]
+    fn test_state_key_ignores_description() {
+        let claim = |description: &str| Claim {
+            description: description.to_string(),
+            claim_type: ClaimType::Information,
+            boldness: 0.4,
+            is_bluff: false,
+        };
+        let state = |description: &str| GameState {
+            round: 3,
+            phase: Phase::Challenge,
+            player1_trust: 60,
+            player2_trust: 45,
+            current_claim: Some(claim(description)),
+            move_history: Vec::new(),
+        };
+
+        assert_eq!(state("first").state_key(), state("second").state_key());
+
+        let mut other = state("first");
+        other.player2_trust += 1;
+        assert_ne!(state("first").state_key(), other.state_key());
+    }
+
+    #[test]
+    fn test_state_keys_collide_within_quantum() {
+        let state = |boldness: f64, confidence: f64| GameState {
+            round: 4,
+            phase: Phase::Challenge,
+            player1_trust: 60,
+            player2_trust: 45,
+            current_claim: Some(Claim {
+                description: String::new(),
+                claim_type: ClaimType::Accusation,
+                boldness,
+                is_bluff: true,
+            }),
+            move_history: vec![Move {
+                action: Action::MakeClaim,
+                player: Player::Player1,
+                claim: None,
+                confidence,
+                trust_after: Some((60, 45)),
+            }],
+        };
+
+        let mut keys = std::collections::HashSet::new();
+        keys.insert(state(0.5, 0.7).key());
+        assert!(keys.contains(&state(0.5004, 0.7003).key()));
+        assert!(!keys.contains(&state(0.501, 0.7).key()));
+        assert!(!keys.contains(&state(0.5, 0.699).key()));
+    }
+
+    #[test]
+    fn test_validate_rejects_out_of_range_values() {
+        let mut state: GameState = serde_json::from_str(
+            r#"{"round":1,"phase":"Challenge","player1_trust":50,"player2_trust":50,"current_claim":{"description":"","claim_type":"Prediction","boldness":1.5,"is_bluff":true},"move_history":[]}"#,
+        )
+        .unwrap();
+        assert_eq!(state.validate(), Err(InvalidState::BoldnessOutOfRange(1.5)));
+
+        state.current_claim.as_mut().unwrap().boldness = 0.5;
+        assert_eq!(state.validate(), Ok(()));
+
+        state.player1_trust = MAX_TRUST + 1;
+        assert_eq!(
+            state.validate(),
+            Err(InvalidState::TrustOutOfRange { player: Player::Player1, trust: MAX_TRUST + 1 })
+        );
+    }
+}
@@ -0,0 +1,428 @@
+use crate::{GameTree, GameState, Move, Player, SearchResult};
+use crate::evaluation::{Evaluator, StateEvaluator};
+use crate::rules::{GameOutcome, GameRules, NoMovePolicy};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::sync::Arc;
+use crate::time::Instant;
+
+/// Upper bound on rollout length, in case a rule set never terminates
+const MAX_ROLLOUT_PLIES: usize = 200;
+
+/// How moves are chosen during simulation rollouts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RolloutPolicy {
+    /// Pick uniformly among the legal moves
+    UniformRandom,
+    /// Pick the move whose expected resulting state evaluates best for the mover
+    Greedy,
+    /// Play greedily, but pick a random move with the given probability
+    EpsilonGreedy(f64),
+}
+
+/// Monte Carlo Tree Search using UCT selection
+pub struct MctsSearch {
+    evaluator: Arc<dyn StateEvaluator>,
+    iterations: u32,
+    exploration_c: f64,
+    rollout_policy: RolloutPolicy,
+    rules: GameRules,
+    seed: Option<u64>,
+}
+
+/// Node of the search tree, reached by playing `move_made`.
+/// Chance outcomes are re-sampled on every visit, so a node stands for a
+/// sequence of moves rather than a single state.
+struct MctsNode {
+    move_made: Option<Move>,
+    children: Vec<usize>,
+    untried: Option<Vec<Move>>,
+    visits: u32,
+    /// Sum of rollout values from the point of view of the player who moved here
+    total_value: f64,
+}
+
+impl MctsNode {
+    fn new(move_made: Option<Move>) -> Self {
+        Self {
+            move_made,
+            children: Vec::new(),
+            untried: None,
+            visits: 0,
+            total_value: 0.0,
+        }
+    }
+
+    fn mean_value(&self) -> f64 {
+        if self.visits == 0 {
+            0.0
+        } else {
+            self.total_value / self.visits as f64
+        }
+    }
+}
+
+impl MctsSearch {
+    pub fn new(iterations: u32, exploration_c: f64) -> Self {
+        Self {
+            evaluator: Arc::new(Evaluator::new()),
+            iterations,
+            exploration_c,
+            rollout_policy: RolloutPolicy::UniformRandom,
+            rules: GameRules::default(),
+            seed: None,
+        }
+    }
+
+    /// Choose how rollouts pick moves. Greedier policies cost an evaluation
+    /// per legal move at every step but give less noisy estimates.
+    pub fn with_rollout_policy(mut self, policy: RolloutPolicy) -> Self {
+        self.rollout_policy = policy;
+        self
+    }
+
+    /// Draw rollout moves and chance outcomes from `seed`, making the search
+    /// reproducible
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn with_evaluator(mut self, evaluator: impl StateEvaluator + 'static) -> Self {
+        self.evaluator = Arc::new(evaluator);
+        self
+    }
+
+    pub fn with_rules(mut self, rules: GameRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Run the configured number of simulations and pick the most visited
+    /// root move. The evaluation is that move's average rollout value on
+    /// the evaluator's scale.
+    pub fn search(&self, state: &GameState, player: Player) -> SearchResult {
+        let start_time = Instant::now();
+        let mut rng = match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
+        let mut tree = GameTree::new_with_rules(state.clone(), self.rules);
+        let mut nodes = vec![MctsNode::new(None)];
+        let mut depth_reached = 0;
+
+        for _ in 0..self.iterations {
+            // Fresh chance outcomes for every simulation
+            tree.set_seed(rng.gen());
+
+            let mut current = state.clone();
+            let mut to_move = player;
+            let mut path = vec![0];
+
+            // Selection and expansion
+            loop {
+                let node = *path.last().unwrap();
+                if tree.is_terminal(&current) {
+                    break;
+                }
+
+                if nodes[node].untried.is_none() {
+                    nodes[node].untried = Some(tree.generate_moves(&current, to_move));
+                }
+
+                if let Some(move_candidate) = nodes[node].untried.as_mut().unwrap().pop() {
+                    tree.make_move(&mut current, &move_candidate);
+                    nodes.push(MctsNode::new(Some(move_candidate)));
+                    let child = nodes.len() - 1;
+                    nodes[node].children.push(child);
+                    path.push(child);
+                    to_move = to_move.opponent();
+                    break;
+                }
+
+                let Some(child) = self.select_child(&nodes, node) else {
+                    break;
+                };
+                tree.make_move(&mut current, nodes[child].move_made.as_ref().unwrap());
+                path.push(child);
+                to_move = to_move.opponent();
+            }
+
+            depth_reached = depth_reached.max(path.len() - 1);
+
+            let value = self.rollout(&tree, current, to_move, player, &mut rng);
+
+            // Backpropagation: even depths were moved into by the opponent
+            for (depth, &node) in path.iter().enumerate() {
+                nodes[node].visits += 1;
+                nodes[node].total_value += if depth % 2 == 1 { value } else { -value };
+            }
+        }
+
+        let best = nodes[0]
+            .children
+            .iter()
+            .copied()
+            .max_by_key(|&child| nodes[child].visits);
+
+        let (best_move, evaluation) = match best {
+            Some(child) => (
+                nodes[child].move_made.clone().unwrap(),
+                nodes[child].mean_value() * 100.0,
+            ),
+            None => (
+                self.default_move(player),
+                self.leaf_value(&tree, state, player) * 100.0,
+            ),
+        };
+
+        // Most visited line below the chosen move
+        let mut principal_variation = vec![best_move.clone()];
+        let mut node = best;
+        while let Some(child) = node.and_then(|n| nodes[n].children.iter().copied().max_by_key(|&c| nodes[c].visits)) {
+            principal_variation.push(nodes[child].move_made.clone().unwrap());
+            node = Some(child);
+        }
+
+        SearchResult {
+            best_move,
+            evaluation,
+            nodes_explored: self.iterations as u64,
+            depth_reached: depth_reached.min(u8::MAX as usize) as u8,
+            time_ms: start_time.elapsed().as_millis() as u64,
+            tt_hits: 0,
+            null_move_cutoffs: 0,
+            principal_variation,
+        }
+    }
+
+    /// UCT child selection from the point of view of the player to move
+    fn select_child(&self, nodes: &[MctsNode], parent: usize) -> Option<usize> {
+        let parent_visits = nodes[parent].visits.max(1) as f64;
+
+        nodes[parent].children.iter().copied().max_by(|&a, &b| {
+            let uct = |child: usize| {
+                let node = &nodes[child];
+                node.mean_value()
+                    + self.exploration_c * (parent_visits.ln() / node.visits.max(1) as f64).sqrt()
+            };
+            uct(a).total_cmp(&uct(b))
+        })
+    }
+
+    /// Play out to the end of the game and score it for `root_player` in [-1, 1]
+    fn rollout(
+        &self,
+        tree: &GameTree,
+        mut state: GameState,
+        mut to_move: Player,
+        root_player: Player,
+        rng: &mut StdRng,
+    ) -> f64 {
+        let mut passed = false;
+
+        for _ in 0..MAX_ROLLOUT_PLIES {
+            if tree.is_terminal(&state) {
+                break;
+            }
+
+            let moves = tree.generate_moves(&state, to_move);
+            if moves.is_empty() {
+                match tree.rules().no_move_policy {
+                    NoMovePolicy::Stalemate => return 0.0,
+                    // Once both players have passed nothing can change any more
+                    NoMovePolicy::Pass if passed => break,
+                    NoMovePolicy::Pass => {
+                        passed = true;
+                        to_move = to_move.opponent();
+                        continue;
+                    }
+                }
+            }
+            passed = false;
+
+            let chosen = self.choose_rollout_move(tree, &state, &moves, to_move, rng);
+            tree.make_move(&mut state, &moves[chosen]);
+            to_move = to_move.opponent();
+        }
+
+        self.leaf_value(tree, &state, root_player)
+    }
+
+    fn choose_rollout_move(
+        &self,
+        tree: &GameTree,
+        state: &GameState,
+        moves: &[Move],
+        player: Player,
+        rng: &mut StdRng,
+    ) -> usize {
+        let greedy = match self.rollout_policy {
+            RolloutPolicy::UniformRandom => false,
+            RolloutPolicy::Greedy => true,
+            RolloutPolicy::EpsilonGreedy(epsilon) => rng.gen::<f64>() >= epsilon,
+        };
+
+        if !greedy {
+            return rng.gen_range(0..moves.len());
+        }
+
+        let expected_value = |m: &Move| -> f64 {
+            tree.resolution_outcomes(state, m)
+                .iter()
+                .map(|(outcome, probability)| self.evaluator.evaluate(outcome, player) * probability)
+                .sum()
+        };
+
+        moves
+            .iter()
+            .map(expected_value)
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(index, _)| index)
+            .unwrap_or(0)
+    }
+
+    /// Value of `state` for `player` in [-1, 1]: decided games score a
+    /// full win or loss, unfinished ones the scaled heuristic
+    fn leaf_value(&self, tree: &GameTree, state: &GameState, player: Player) -> f64 {
+        match tree.outcome(state) {
+            Some(GameOutcome::Draw) => 0.0,
+            Some(GameOutcome::Win(winner)) if winner == player => 1.0,
+            Some(GameOutcome::Win(_)) => -1.0,
+            _ => self.evaluator.evaluate(state, player) / 100.0,
+        }
+    }
+
+    fn default_move(&self, player: Player) -> Move {
+        Move {
+            action: crate::Action::Accept,
+            player,
+            claim: None,
+            confidence: 0.5,
+            trust_after: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Phase;
+
+    fn create_test_state() -> GameState {
+        GameState {
+            round: 10,
+            phase: Phase::Claim,
+            player1_trust: 50,
+            player2_trust: 50,
+            current_claim: None,
+            move_history: Vec::new(),
+        }
+    }
+
+    fn estimate_variance(policy: RolloutPolicy) -> f64 {
+        // Player 1 trails with the whole game ahead, so how the rollouts
+        // play out swings the value a lot
+        let mut state = create_test_state();
+        state.round = 1;
+        state.player1_trust = 30;
+        state.player2_trust = 60;
+
+        // Sample rollouts directly rather than through whole searches,
+        // whose averaging hides most of the difference between policies
+        let search = MctsSearch::new(1, 1.4).with_rollout_policy(policy);
+        let mut tree = GameTree::new(state.clone());
+        let mut rng = StdRng::seed_from_u64(17);
+        let values: Vec<f64> = (0..400)
+            .map(|_| {
+                tree.set_seed(rng.gen());
+                search.rollout(&tree, state.clone(), Player::Player1, Player::Player1, &mut rng)
+            })
+            .collect();
+
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64
+    }
+
+    #[test]
+    fn test_mcts_search() {
+        let search = MctsSearch::new(100, 1.4).with_seed(7);
+        let result = search.search(&create_test_state(), Player::Player1);
+
+        assert_eq!(result.nodes_explored, 100);
+        assert_eq!(result.best_move.player, Player::Player1);
+    }
+
+    #[test]
+    fn test_prefers_dominating_move() {
+        // Last round, player 2 narrowly behind and facing a bold claim:
+        // accepting hands player 1 the game, while challenging usually wins it
+        let claim = crate::Claim {
+            description: String::new(),
+            claim_type: crate::ClaimType::Accusation,
+            boldness: 1.0,
+            is_bluff: true,
+        };
+        let mut state = create_test_state();
+        state.round = 19;
+        state.phase = Phase::Challenge;
+        state.player1_trust = 52;
+        state.current_claim = Some(claim.clone());
+        state.move_history.push(Move {
+            action: crate::Action::MakeClaim,
+            player: Player::Player1,
+            claim: Some(claim),
+            confidence: 0.7,
+            trust_after: None,
+        });
+
+        let result = MctsSearch::new(400, 1.4).with_seed(3).search(&state, Player::Player2);
+        assert_eq!(result.best_move.action, crate::Action::Challenge);
+        assert!(result.evaluation > 0.0);
+    }
+
+    #[test]
+    fn test_prefers_narrow_round_limit_win() {
+        // Last round, player 2 narrowly ahead: accepting ends the game won
+        // by 2, while challenging mostly goes nowhere and plays on
+        let claim = crate::Claim {
+            description: String::new(),
+            claim_type: crate::ClaimType::Information,
+            boldness: 0.2,
+            is_bluff: false,
+        };
+        let mut state = create_test_state();
+        state.round = 19;
+        state.phase = Phase::Challenge;
+        state.player1_trust = 45;
+        state.player2_trust = 52;
+        state.current_claim = Some(claim.clone());
+        state.move_history.push(Move {
+            action: crate::Action::MakeClaim,
+            player: Player::Player1,
+            claim: Some(claim),
+            confidence: 0.7,
+            trust_after: None,
+        });
+        let rules = GameRules {
+            inconclusive_probability: 0.9,
+            ..GameRules::default()
+        };
+
+        let result = MctsSearch::new(400, 1.4).with_rules(rules).with_seed(5).search(&state, Player::Player2);
+        assert_eq!(result.best_move.action, crate::Action::Accept);
+        assert_eq!(result.evaluation, 100.0);
+    }
+
+    #[test]
+    fn test_greedy_rollouts_reduce_variance() {
+        let uniform = estimate_variance(RolloutPolicy::UniformRandom);
+        let greedy = estimate_variance(RolloutPolicy::Greedy);
+        let epsilon = estimate_variance(RolloutPolicy::EpsilonGreedy(0.2));
+
+        assert!(greedy < epsilon, "greedy {greedy} vs epsilon {epsilon}");
+        assert!(epsilon < uniform, "epsilon {epsilon} vs uniform {uniform}");
+    }
+}
@@ -1,115 +1,310 @@
-use crate::{GameTree, GameState, Move, Player, SearchResult};
-use crate::evaluation::Evaluator;
-use std::time::Instant;
+use crate::{ClaimLevels, GameTree, GameState, Move, Phase, Player, SearchResult};
+use crate::evaluation::{Evaluator, StateEvaluator};
+use crate::rules::{mate_value, GameOutcome, GameRules, NoMovePolicy};
+use std::sync::Arc;
+use crate::time::Instant;
 
 /// Minimax search algorithm implementation
 pub struct MinimaxSearch {
-    evaluator: Evaluator,
+    evaluator: Arc<dyn StateEvaluator>,
     max_depth: u8,
     nodes_explored: u64,
+    /// Least remaining depth of any node searched, so the search reached
+    /// its depth minus this many plies
+    min_remaining_depth: u8,
+    /// Move to try first at the root, taken by the next root expansion
+    root_hint: Option<Move>,
+    /// Seed for challenge resolution; entropy if unset
+    seed: Option<u64>,
+    rules: GameRules,
+    /// Boldness levels of both players' claims, overriding the risk profiles
+    claim_levels: Option<ClaimLevels>,
+    /// Claims bolder than this can only be challenged
+    accept_limit: Option<f64>,
+    /// Zobrist hash and player to move of each position from the start of
+    /// the game down to the node being searched, for detecting repetitions
+    positions: Vec<(u64, Player)>,
 }
 
 impl MinimaxSearch {
     pub fn new(max_depth: u8) -> Self {
         Self {
-            evaluator: Evaluator::new(),
+            evaluator: Arc::new(Evaluator::new()),
             max_depth,
             nodes_explored: 0,
+            min_remaining_depth: 0,
+            root_hint: None,
+            seed: None,
+            rules: GameRules::default(),
+            claim_levels: None,
+            accept_limit: None,
+            positions: Vec::new(),
         }
     }
 
+    /// Resolve challenges deterministically from `seed`, making repeated
+    /// searches of the same state explore exactly the same tree
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Search under a custom rule set instead of the standard thresholds
+    pub fn with_rules(mut self, rules: GameRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Let both players claim at `levels` only, as
+    /// `GameTree::set_claim_levels` does
+    pub fn with_claim_levels(mut self, levels: ClaimLevels) -> Self {
+        self.claim_levels = Some(levels);
+        self
+    }
+
+    /// Never accept a claim bolder than `limit`, as
+    /// `GameTree::set_accept_limit` does
+    pub fn with_accept_limit(mut self, limit: f64) -> Self {
+        self.accept_limit = Some(limit);
+        self
+    }
+
+    fn build_tree(&self, state: &GameState) -> GameTree {
+        let mut tree = GameTree::new_with_rules(state.clone(), self.rules);
+        if let Some(seed) = self.seed {
+            tree.set_seed(seed);
+        }
+        if let Some(levels) = &self.claim_levels {
+            tree.set_claim_levels(levels.clone());
+        }
+        tree.set_accept_limit(self.accept_limit);
+        tree
+    }
+
+    /// Score leaves with a custom evaluator, e.g. an `Evaluator` with a
+    /// different weight set or competitiveness, or any other `StateEvaluator`
+    pub fn with_evaluator(mut self, evaluator: impl StateEvaluator + 'static) -> Self {
+        self.evaluator = Arc::new(evaluator);
+        self
+    }
+
     pub fn search(&mut self, state: &GameState, player: Player) -> SearchResult {
         let start_time = Instant::now();
         self.nodes_explored = 0;
+        self.min_remaining_depth = self.max_depth;
 
-        let tree = GameTree::new(state.clone());
-        let (best_move, evaluation) = self.minimax(&tree, state, self.max_depth, player, true);
+        let tree = self.build_tree(state);
+        self.positions = tree.history_positions(state);
+        let mut line = Vec::new();
+        let (best_move, evaluation) = self.minimax(&tree, state, self.max_depth, 0, player, true, &mut line);
 
         let time_ms = start_time.elapsed().as_millis() as u64;
+        let best_move = best_move.unwrap_or_else(|| self.default_move(state, player));
+        if line.is_empty() {
+            line.push(best_move.clone());
+        }
 
         SearchResult {
-            best_move: best_move.unwrap_or_else(|| self.default_move(state, player)),
+            best_move,
             evaluation,
             nodes_explored: self.nodes_explored,
-            depth_reached: self.max_depth,
+            depth_reached: self.max_depth - self.min_remaining_depth,
             time_ms,
+            tt_hits: 0,
+            null_move_cutoffs: 0,
+            principal_variation: line,
+        }
+    }
+
+    /// Search depth 1, 2 and so on up to `max_depth`, keeping the best move
+    /// of the deepest completed iteration. Each iteration tries the previous
+    /// iteration's best move first. `depth_reached` is how deep the deepest
+    /// completed iteration actually went, and `nodes_explored` counts every
+    /// iteration.
+    pub fn search_iterative(&mut self, state: &GameState, player: Player, max_depth: u8) -> SearchResult {
+        let start_time = Instant::now();
+        let tree = self.build_tree(state);
+        self.positions = tree.history_positions(state);
+
+        let mut total_nodes = 0;
+        let mut completed: Option<(Move, f64, u8)> = None;
+        let mut completed_line = Vec::new();
+        // Value of a root without moves, such as a finished game
+        let mut root_value = None;
+
+        for depth in 1..=max_depth {
+            self.nodes_explored = 0;
+            self.min_remaining_depth = depth;
+            self.root_hint = completed.as_ref().map(|(m, _, _)| m.clone());
+
+            let mut line = Vec::new();
+            let (best_move, evaluation) = self.minimax(&tree, state, depth, 0, player, true, &mut line);
+            total_nodes += self.nodes_explored;
+
+            match best_move {
+                Some(m) => {
+                    completed = Some((m, evaluation, depth - self.min_remaining_depth));
+                    completed_line = line;
+                }
+                // No moves at the root: deeper iterations cannot change anything
+                None => {
+                    root_value = Some(evaluation);
+                    break;
+                }
+            }
+        }
+
+        self.root_hint = None;
+        self.nodes_explored = total_nodes;
+
+        let (best_move, evaluation, depth_reached) = completed.unwrap_or_else(|| {
+            let evaluation = root_value.unwrap_or_else(|| self.evaluator.evaluate(state, player));
+            (self.default_move(state, player), evaluation, 0)
+        });
+        if completed_line.is_empty() {
+            completed_line.push(best_move.clone());
+        }
+
+        SearchResult {
+            best_move,
+            evaluation,
+            nodes_explored: total_nodes,
+            depth_reached,
+            time_ms: start_time.elapsed().as_millis() as u64,
+            tt_hits: 0,
+            null_move_cutoffs: 0,
+            principal_variation: completed_line,
         }
     }
 
+    /// Value of `state` to `player`, who is to move, and their best move
+    /// in it, with the line of play expected from here left in `line`.
+    /// `state` is `ply` plies below the root, and `root_side` says whether
+    /// `player` is the searching player.
+    #[allow(clippy::too_many_arguments)]
     fn minimax(
         &mut self,
         tree: &GameTree,
         state: &GameState,
         depth: u8,
+        ply: usize,
+        player: Player,
+        root_side: bool,
+        line: &mut Vec<Move>,
+    ) -> (Option<Move>, f64) {
+        self.positions.push((state.zobrist_hash(), player));
+        let result = self.search_node(tree, state, depth, ply, player, root_side, line);
+        self.positions.pop();
+        result
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn search_node(
+        &mut self,
+        tree: &GameTree,
+        state: &GameState,
+        depth: u8,
+        ply: usize,
         player: Player,
-        is_maximizing: bool,
+        root_side: bool,
+        line: &mut Vec<Move>,
     ) -> (Option<Move>, f64) {
+        line.clear();
         self.nodes_explored += 1;
+        self.min_remaining_depth = self.min_remaining_depth.min(depth);
+
+        // Leaves are always scored from the searching player's point of
+        // view and negated at the opponent's nodes
+        let perspective = if root_side { player } else { player.opponent() };
+        let sign = if root_side { 1.0 } else { -1.0 };
 
         // Terminal conditions
-        if depth == 0 || tree.is_terminal(state) {
-            let eval = self.evaluator.evaluate(state, player);
-            return (None, eval);
+        match tree.outcome(state) {
+            // A drawn game is worth nothing to either side
+            Some(GameOutcome::Draw) => return (None, 0.0),
+            Some(GameOutcome::Win(winner)) => return (None, mate_value(winner == player, ply)),
+            // Repeating a position is as good as a draw
+            None if tree.is_draw(state, player, &self.positions) => return (None, 0.0),
+            // A pending challenge is about to swing trust, so settle it first
+            None if depth == 0 && state.phase == Phase::Challenge => {
+                return (None, self.quiescence(tree, state, ply, player, root_side));
+            }
+            None if depth == 0 => return (None, sign * self.evaluator.evaluate(state, perspective)),
+            None => {}
         }
 
-        let moves = tree.generate_moves(state, player);
+        let mut moves = tree.generate_moves(state, player);
+
+        // Only the root is expanded before the hint is taken
+        if let Some(hint) = self.root_hint.take() {
+            if let Some(index) = moves.iter().position(|m| *m == hint) {
+                let first = moves.remove(index);
+                moves.insert(0, first);
+            }
+        }
 
         if moves.is_empty() {
-            let eval = self.evaluator.evaluate(state, player);
+            // Non-terminal with nothing to play: see `NoMovePolicy`
+            let eval = match tree.rules().no_move_policy {
+                NoMovePolicy::Stalemate => 0.0,
+                NoMovePolicy::Pass => {
+                    -self.minimax(tree, state, depth - 1, ply + 1, player.opponent(), !root_side, line).1
+                }
+            };
             return (None, eval);
         }
 
-        if is_maximizing {
-            let mut max_eval = f64::NEG_INFINITY;
-            let mut best_move = None;
-
-            for move_candidate in moves {
-                let new_state = tree.apply_move(state, &move_candidate);
-                let (_, eval) = self.minimax(
-                    tree,
-                    &new_state,
-                    depth - 1,
-                    player.opponent(),
-                    false,
-                );
-
-                if eval > max_eval {
-                    max_eval = eval;
-                    best_move = Some(move_candidate);
-                }
-            }
+        let mut child_line = Vec::new();
+        let mut best_eval = f64::NEG_INFINITY;
+        let mut best_move = None;
 
-            (best_move, max_eval)
-        } else {
-            let mut min_eval = f64::INFINITY;
-            let mut best_move = None;
-
-            for move_candidate in moves {
-                let new_state = tree.apply_move(state, &move_candidate);
-                let (_, eval) = self.minimax(
-                    tree,
-                    &new_state,
-                    depth - 1,
-                    player.opponent(),
-                    true,
-                );
-
-                if eval < min_eval {
-                    min_eval = eval;
-                    best_move = Some(move_candidate);
-                }
-            }
+        for move_candidate in moves {
+            let mut new_state = state.clone();
+            tree.make_move(&mut new_state, &move_candidate);
+            let (_, reply_eval) = self.minimax(
+                tree,
+                &new_state,
+                depth - 1,
+                ply + 1,
+                player.opponent(),
+                !root_side,
+                &mut child_line,
+            );
+            let eval = -reply_eval;
 
-            (best_move, min_eval)
+            if eval > best_eval {
+                best_eval = eval;
+                line.clear();
+                line.push(move_candidate.clone());
+                line.append(&mut child_line);
+                best_move = Some(move_candidate);
+            }
         }
+
+        (best_move, best_eval)
     }
 
-    fn default_move(&self, state: &GameState, player: Player) -> Move {
+    /// Value of a leaf at which a claim awaits its reply: the best reply,
+    /// scored once the claim is resolved, as alpha-beta's quiescence does
+    fn quiescence(&mut self, tree: &GameTree, state: &GameState, ply: usize, player: Player, root_side: bool) -> f64 {
+        let mut reply_line = Vec::new();
+        tree.generate_moves(state, player)
+            .iter()
+            .map(|reply| {
+                let mut new_state = state.clone();
+                tree.make_move(&mut new_state, reply);
+                -self.minimax(tree, &new_state, 0, ply + 1, player.opponent(), !root_side, &mut reply_line).1
+            })
+            .fold(f64::NEG_INFINITY, f64::max)
+    }
+
+    fn default_move(&self, _state: &GameState, player: Player) -> Move {
         Move {
             action: crate::Action::Accept,
             player,
             claim: None,
             confidence: 0.5,
+            trust_after: None,
         }
     }
 }
@@ -139,4 +334,134 @@ mod tests {
         assert!(result.nodes_explored > 0);
         assert!(result.depth_reached > 0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_custom_evaluator_scores_leaves() {
+        struct TrustDifferential;
+
+        impl StateEvaluator for TrustDifferential {
+            fn evaluate(&self, state: &GameState, player: Player) -> f64 {
+                let differential = (state.player1_trust - state.player2_trust) as f64;
+                if player == Player::Player1 { differential } else { -differential }
+            }
+        }
+
+        // Answering no claim at all only moves on to the next round, so the
+        // leaf keeps the root's trust
+        let mut state = create_test_state();
+        state.phase = Phase::Challenge;
+        state.player1_trust = 65;
+        let mut search = MinimaxSearch::new(1).with_evaluator(TrustDifferential);
+        assert_eq!(search.search(&state, Player::Player1).evaluation, 15.0);
+    }
+
+    #[test]
+    fn test_depth_reached_stops_at_game_end() {
+        // The claim and its answer end the game at the round limit
+        let mut state = create_test_state();
+        state.round = 19;
+
+        let mut search = MinimaxSearch::new(5);
+        assert_eq!(search.search(&state, Player::Player1).depth_reached, 2);
+        assert_eq!(search.search_iterative(&state, Player::Player1, 5).depth_reached, 2);
+    }
+
+    #[test]
+    fn test_search_iterative_reports_completed_depth() {
+        let mut search = MinimaxSearch::new(3);
+        let state = create_test_state();
+
+        let result = search.search_iterative(&state, Player::Player1, 3);
+        assert_eq!(result.depth_reached, 3);
+        assert_eq!(result.best_move.player, Player::Player1);
+        assert_eq!(result.principal_variation.first(), Some(&result.best_move));
+        assert!(result.principal_variation.len() <= 3);
+
+        // Nothing to play at the root, so no iteration completes
+        let mut resolution = create_test_state();
+        resolution.phase = Phase::Resolution;
+        let result = search.search_iterative(&resolution, Player::Player1, 3);
+        assert_eq!(result.depth_reached, 0);
+        assert!(result.nodes_explored > 0);
+    }
+
+    #[test]
+    fn test_seeded_searches_repeat() {
+        let state = create_test_state();
+        let search = || MinimaxSearch::new(3).with_seed(9).search(&state, Player::Player1);
+
+        let (first, second) = (search(), search());
+        assert_eq!(first.best_move, second.best_move);
+        assert_eq!(first.evaluation, second.evaluation);
+        assert_eq!(first.principal_variation, second.principal_variation);
+    }
+
+    #[test]
+    fn test_mate_distance_prefers_shorter_win() {
+        // Player 2 wins on the lead whenever the game reaches round 20.
+        // Accepting gets there at once; an inconclusive challenge keeps
+        // round 19 and wins only after another claim and reply.
+        let rules = GameRules {
+            win_trust: 1000,
+            lose_trust: -1000,
+            inconclusive_probability: 1.0,
+            ..GameRules::default()
+        };
+        let mut state = create_test_state();
+        state.round = 19;
+        state.phase = Phase::Challenge;
+        state.player1_trust = 10;
+        state.player2_trust = 80;
+        state.current_claim = Some(crate::Claim {
+            description: "claim".to_string(),
+            claim_type: crate::ClaimType::Information,
+            boldness: 0.5,
+            is_bluff: false,
+        });
+
+        let result = MinimaxSearch::new(4).with_rules(rules).search(&state, Player::Player2);
+        assert_eq!(result.best_move.action, crate::Action::Accept);
+        assert_eq!(result.evaluation, crate::MATE_SCORE - 1.0);
+
+        // Finished games are scored like alpha-beta scores them
+        let mut near_win = create_test_state();
+        near_win.round = 19;
+        near_win.player1_trust = 95;
+        let minimax = MinimaxSearch::new(3).with_seed(1).search(&near_win, Player::Player1);
+        let alpha_beta = crate::AlphaBetaSearch::new(3, false).with_seed(1).search(&near_win, Player::Player1);
+        assert_eq!(minimax.evaluation, alpha_beta.evaluation);
+    }
+
+    #[test]
+    fn test_claim_levels_replace_profile_levels() {
+        let levels = ClaimLevels::new(vec![0.6], 0.5).unwrap();
+        let result = MinimaxSearch::new(3)
+            .with_seed(2)
+            .with_claim_levels(levels)
+            .search(&create_test_state(), Player::Player1);
+
+        let claim = result.best_move.claim.unwrap();
+        assert_eq!(claim.boldness, 0.6);
+        assert!(claim.is_bluff);
+    }
+
+    #[test]
+    fn test_accept_limit_forces_challenge() {
+        let mut state = create_test_state();
+        state.phase = Phase::Challenge;
+        state.current_claim = Some(crate::Claim {
+            description: "Bold claim".to_string(),
+            claim_type: crate::ClaimType::Accusation,
+            boldness: 0.8,
+            is_bluff: false,
+        });
+
+        // Near a loss, player 2 would rather not risk a failed challenge
+        state.player2_trust = -40;
+        let open = MinimaxSearch::new(2).with_seed(1).search(&state, Player::Player2);
+        assert_eq!(open.best_move.action, crate::Action::Accept);
+
+        let limited = MinimaxSearch::new(2).with_seed(1).with_accept_limit(0.5).search(&state, Player::Player2);
+        assert_eq!(limited.best_move.action, crate::Action::Challenge);
+    }
+}
@@ -0,0 +1,151 @@
+use crate::{GameState, Move, Phase, Player};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Width of a trust bucket in a default book
+const DEFAULT_BUCKET_SIZE: i32 = 5;
+
+/// What a book entry is looked up by: the round, the phase and which
+/// bucket of `bucket_size` trust points each player's trust falls in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BookKey {
+    pub round: u8,
+    pub phase: Phase,
+    pub player1_bucket: i32,
+    pub player2_bucket: i32,
+}
+
+/// Precomputed best moves for early positions, so they need not be searched
+/// from scratch every game. Positions that differ only in the move history,
+/// the pending claim or trust within a bucket share an entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "BookFile", into = "BookFile")]
+pub struct OpeningBook {
+    bucket_size: i32,
+    entries: HashMap<BookKey, Move>,
+}
+
+/// Serialized form of a book: JSON objects cannot have structs as keys
+#[derive(Serialize, Deserialize)]
+struct BookFile {
+    bucket_size: i32,
+    entries: Vec<BookEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BookEntry {
+    #[serde(flatten)]
+    key: BookKey,
+    best_move: Move,
+}
+
+impl OpeningBook {
+    /// Empty book grouping trust into buckets of `bucket_size` points,
+    /// at least 1
+    pub fn new(bucket_size: i32) -> Self {
+        Self {
+            bucket_size: bucket_size.max(1),
+            entries: HashMap::new(),
+        }
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    pub fn key(&self, state: &GameState) -> BookKey {
+        BookKey {
+            round: state.round,
+            phase: state.phase,
+            player1_bucket: state.player1_trust.div_euclid(self.bucket_size),
+            player2_bucket: state.player2_trust.div_euclid(self.bucket_size),
+        }
+    }
+
+    /// Record `best_move` for `state`, replacing any earlier entry for its key
+    pub fn insert(&mut self, state: &GameState, best_move: Move) {
+        self.entries.insert(self.key(state), best_move);
+    }
+
+    /// Book move for `player` in `state`, if there is one for them
+    pub fn lookup(&self, state: &GameState, player: Player) -> Option<&Move> {
+        self.entries.get(&self.key(state)).filter(|m| m.player == player)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl Default for OpeningBook {
+    fn default() -> Self {
+        Self::new(DEFAULT_BUCKET_SIZE)
+    }
+}
+
+impl From<BookFile> for OpeningBook {
+    fn from(file: BookFile) -> Self {
+        let mut book = OpeningBook::new(file.bucket_size);
+        book.entries = file.entries.into_iter().map(|entry| (entry.key, entry.best_move)).collect();
+        book
+    }
+}
+
+impl From<OpeningBook> for BookFile {
+    fn from(book: OpeningBook) -> Self {
+        BookFile {
+            bucket_size: book.bucket_size,
+            entries: book
+                .entries
+                .into_iter()
+                .map(|(key, best_move)| BookEntry { key, best_move })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Action;
+
+    fn create_test_state() -> GameState {
+        GameState {
+            round: 1,
+            phase: Phase::Challenge,
+            player1_trust: 50,
+            player2_trust: 50,
+            current_claim: None,
+            move_history: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_book_json_round_trip() {
+        let challenge = Move {
+            action: Action::Challenge,
+            player: Player::Player2,
+            claim: None,
+            confidence: 0.8,
+            trust_after: None,
+        };
+        let mut book = OpeningBook::new(10);
+        book.insert(&create_test_state(), challenge.clone());
+
+        let restored = OpeningBook::from_json(&serde_json::to_string(&book).unwrap()).unwrap();
+        assert_eq!(restored.len(), 1);
+
+        // Same buckets, so the same entry
+        let mut nearby = create_test_state();
+        nearby.player1_trust = 57;
+        assert_eq!(restored.lookup(&nearby, Player::Player2), Some(&challenge));
+        assert_eq!(restored.lookup(&nearby, Player::Player1), None);
+
+        nearby.player2_trust = 60;
+        assert_eq!(restored.lookup(&nearby, Player::Player2), None);
+    }
+}
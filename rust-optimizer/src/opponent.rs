@@ -0,0 +1,112 @@
+use crate::Claim;
+use serde::{Deserialize, Serialize};
+
+/// What the engine assumes about its opponent when the opponent answers a
+/// claim, in place of a perfectly informed minimizing reply
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct OpponentModel {
+    /// Chance in `[0, 1]` that the opponent sees through to whether a claim
+    /// is a bluff; otherwise they judge it by its boldness alone
+    bluff_detection_skill: f64,
+}
+
+impl OpponentModel {
+    pub fn new(bluff_detection_skill: f64) -> Self {
+        Self {
+            bluff_detection_skill: bluff_detection_skill.clamp(0.0, 1.0),
+        }
+    }
+
+    pub fn bluff_detection_skill(&self) -> f64 {
+        self.bluff_detection_skill
+    }
+
+    /// Probability that the opponent challenges `claim`.
+    ///
+    /// An opponent who sees through the claim challenges exactly the
+    /// bluffs. One who does not challenges with probability equal to the
+    /// claim's boldness, bluff or not. At skill 1 bluffs are always
+    /// challenged and honest claims never are; at skill 0 only boldness
+    /// matters.
+    pub fn challenge_probability(&self, claim: &Claim) -> f64 {
+        let detected = if claim.is_bluff { 1.0 } else { 0.0 };
+        let guessed = claim.boldness.clamp(0.0, 1.0);
+
+        self.bluff_detection_skill * detected + (1.0 - self.bluff_detection_skill) * guessed
+    }
+}
+
+impl Default for OpponentModel {
+    /// A perfect bluff detector
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AlphaBetaSearch, ClaimType, GameState, Phase, Player};
+
+    fn create_test_state() -> GameState {
+        GameState {
+            round: 1,
+            phase: Phase::Claim,
+            player1_trust: 50,
+            player2_trust: 50,
+            current_claim: None,
+            move_history: Vec::new(),
+        }
+    }
+
+    fn create_claim(boldness: f64, is_bluff: bool) -> Claim {
+        Claim {
+            description: String::new(),
+            claim_type: ClaimType::Prediction,
+            boldness,
+            is_bluff,
+        }
+    }
+
+    #[test]
+    fn test_skill_sharpens_bluff_detection() {
+        let novice = OpponentModel::new(0.1);
+        let expert = OpponentModel::new(0.9);
+        let bluff = create_claim(0.6, true);
+        let honest = create_claim(0.4, false);
+
+        assert!(novice.challenge_probability(&bluff) < expert.challenge_probability(&bluff));
+        assert!(novice.challenge_probability(&honest) > expert.challenge_probability(&honest));
+
+        assert_eq!(OpponentModel::new(1.0).challenge_probability(&bluff), 1.0);
+        assert_eq!(OpponentModel::new(1.0).challenge_probability(&honest), 0.0);
+        assert!((OpponentModel::new(0.0).challenge_probability(&bluff) - 0.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_weak_detector_makes_bluffing_pay() {
+        // How much better bluffs score than honest claims on average, over
+        // enough seeds to even out sampled challenge outcomes
+        let bluff_edge = |skill: f64| {
+            let mut edge = 0.0;
+            for seed in 0..32 {
+                let mut search = AlphaBetaSearch::new(2, false)
+                    .with_seed(seed)
+                    .with_opponent_model(OpponentModel::new(skill));
+                let scored = search.search_scored(&create_test_state(), Player::Player1, 2);
+                let mean = |bluff: bool| {
+                    let values: Vec<f64> = scored
+                        .iter()
+                        .filter(|(m, _)| m.claim.as_ref().is_some_and(|c| c.is_bluff == bluff))
+                        .map(|(_, eval)| *eval)
+                        .collect();
+                    values.iter().sum::<f64>() / values.len() as f64
+                };
+                edge += mean(true) - mean(false);
+            }
+            edge
+        };
+
+        assert!(bluff_edge(0.1) > bluff_edge(0.9));
+    }
+}
@@ -0,0 +1,184 @@
+use crate::{Action, GameState, Move};
+use serde::{Deserialize, Serialize};
+
+/// How much risk a player takes: the boldness of the claims generated for
+/// them and, for a searching engine, how it values the game going on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum RiskProfile {
+    Cautious,
+    #[default]
+    Balanced,
+    Aggressive,
+}
+
+impl RiskProfile {
+    /// Boldness of the claims generated for a player with this profile,
+    /// one claim of each type per level
+    pub fn boldness_levels(&self) -> [f64; 4] {
+        match self {
+            RiskProfile::Cautious => [0.1, 0.2, 0.3, 0.4],
+            RiskProfile::Balanced => [0.2, 0.4, 0.6, 0.8],
+            RiskProfile::Aggressive => [0.5, 0.6, 0.8, 0.9],
+        }
+    }
+
+    /// Constant added to every evaluation from the searching player's point
+    /// of view. Drawn and decided games are scored without it, so a positive
+    /// contempt plays on rather than settle for a draw and a negative one
+    /// takes the draw.
+    pub fn contempt(&self) -> f64 {
+        match self {
+            RiskProfile::Cautious => -5.0,
+            RiskProfile::Balanced => 0.0,
+            RiskProfile::Aggressive => 5.0,
+        }
+    }
+
+    /// Numeric id used across the FFI boundary: 0 cautious, 1 balanced,
+    /// 2 aggressive
+    pub fn to_id(&self) -> u8 {
+        match self {
+            RiskProfile::Cautious => 0,
+            RiskProfile::Balanced => 1,
+            RiskProfile::Aggressive => 2,
+        }
+    }
+
+    /// Inverse of `to_id`; any other id is rejected
+    pub fn from_id(id: u8) -> Option<RiskProfile> {
+        match id {
+            0 => Some(RiskProfile::Cautious),
+            1 => Some(RiskProfile::Balanced),
+            2 => Some(RiskProfile::Aggressive),
+            _ => None,
+        }
+    }
+}
+
+/// How boldly the engine assumes its opponent plays. Where the opponent is
+/// to move, the search only considers the moves of this profile, so the
+/// engine can exploit an opponent it expects to shy away from challenges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BoldnessProfile {
+    /// Makes only modest claims and challenges only very bold ones
+    Timid,
+    /// Plays any move
+    #[default]
+    Neutral,
+    /// Makes only bold claims and accepts only modest ones
+    Bold,
+}
+
+impl BoldnessProfile {
+    /// Inclusive range of boldness the profile makes claims at
+    fn claim_boldness(&self) -> (f64, f64) {
+        match self {
+            BoldnessProfile::Timid => (0.0, 0.4),
+            BoldnessProfile::Neutral => (0.0, 1.0),
+            BoldnessProfile::Bold => (0.6, 1.0),
+        }
+    }
+
+    /// Least boldness of a claim the profile challenges
+    fn challenges_from(&self) -> f64 {
+        match self {
+            BoldnessProfile::Timid => 0.7,
+            BoldnessProfile::Neutral | BoldnessProfile::Bold => 0.0,
+        }
+    }
+
+    /// Greatest boldness of a claim the profile accepts
+    fn accepts_up_to(&self) -> f64 {
+        match self {
+            BoldnessProfile::Timid | BoldnessProfile::Neutral => 1.0,
+            BoldnessProfile::Bold => 0.3,
+        }
+    }
+
+    /// Whether a player with this profile would play `m` in `state`
+    pub fn allows(&self, state: &GameState, m: &Move) -> bool {
+        let standing = state.current_claim.as_ref().map(|claim| claim.boldness);
+
+        match m.action {
+            Action::MakeClaim => {
+                let (low, high) = self.claim_boldness();
+                m.claim.as_ref().is_none_or(|claim| (low..=high).contains(&claim.boldness))
+            }
+            Action::Challenge => standing.is_none_or(|boldness| boldness >= self.challenges_from()),
+            Action::Accept => standing.is_none_or(|boldness| boldness <= self.accepts_up_to()),
+        }
+    }
+
+    /// Drop the moves of `moves` the profile would not play in `state`,
+    /// unless that would leave none
+    pub fn restrict(&self, state: &GameState, moves: &mut Vec<Move>) {
+        if moves.iter().any(|m| self.allows(state, m)) {
+            moves.retain(|m| self.allows(state, m));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AlphaBetaSearch, Phase, Player};
+
+    fn create_test_state() -> GameState {
+        GameState {
+            round: 1,
+            phase: Phase::Claim,
+            player1_trust: 50,
+            player2_trust: 50,
+            current_claim: None,
+            move_history: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_profiles_pick_different_claims() {
+        let claim_boldness = |profile: RiskProfile| {
+            let mut search = AlphaBetaSearch::new(3, false).with_seed(7).with_risk_profile(profile);
+            let best_move = search.search(&create_test_state(), Player::Player1).best_move;
+            assert_eq!(best_move.action, Action::MakeClaim);
+            best_move.claim.unwrap().boldness
+        };
+
+        let cautious = claim_boldness(RiskProfile::Cautious);
+        let aggressive = claim_boldness(RiskProfile::Aggressive);
+        assert!(RiskProfile::Cautious.boldness_levels().contains(&cautious));
+        assert!(RiskProfile::Aggressive.boldness_levels().contains(&aggressive));
+        assert!(aggressive > cautious);
+    }
+
+    #[test]
+    fn test_boldness_profiles_restrict_replies() {
+        let mut state = create_test_state();
+        state.phase = Phase::Challenge;
+        state.current_claim = Some(crate::Claim {
+            description: "claim".to_string(),
+            claim_type: crate::ClaimType::Information,
+            boldness: 0.4,
+            is_bluff: false,
+        });
+        let tree = crate::GameTree::new(state.clone());
+
+        let mut moves = tree.generate_moves(&state, Player::Player2);
+        BoldnessProfile::Timid.restrict(&state, &mut moves);
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0].action, crate::Action::Accept);
+
+        // A bold opponent accepts nothing this bold, but still answers
+        let mut moves = tree.generate_moves(&state, Player::Player2);
+        BoldnessProfile::Bold.restrict(&state, &mut moves);
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0].action, crate::Action::Challenge);
+    }
+
+    #[test]
+    fn test_profile_ids_round_trip() {
+        for profile in [RiskProfile::Cautious, RiskProfile::Balanced, RiskProfile::Aggressive] {
+            assert_eq!(RiskProfile::from_id(profile.to_id()), Some(profile));
+        }
+        assert_eq!(RiskProfile::from_id(3), None);
+    }
+}
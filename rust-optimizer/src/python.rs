@@ -0,0 +1,193 @@
+//! Python bindings (only compiled when the 'python' feature is enabled).
+//! States and results cross the boundary as native Python dicts and
+//! objects, converted through the same serde representation the JSON FFI
+//! uses.
+
+use crate::ffi::{truncate_descriptions, MAX_DESCRIPTION_LEN};
+use crate::{AlphaBetaSearch, Player};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::sync::atomic::Ordering;
+
+/// Convert a serializable value into plain Python dicts, lists and scalars
+fn to_python(py: Python<'_>, value: &impl Serialize) -> PyResult<PyObject> {
+    let json = serde_json::to_string(value).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(py.import("json")?.call_method1("loads", (json,))?.unbind())
+}
+
+/// Read a value from plain Python dicts, lists and scalars
+fn from_python<T: DeserializeOwned>(object: &Bound<'_, PyAny>) -> PyResult<T> {
+    let json: String = object.py().import("json")?.call_method1("dumps", (object,))?.extract()?;
+    serde_json::from_str(&json).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+fn player_from_id(player: u8) -> PyResult<Player> {
+    Player::from_id(player).ok_or_else(|| PyValueError::new_err(format!("invalid player {}", player)))
+}
+
+/// Read a game state, refusing one with out-of-range values and truncating
+/// claim descriptions like the C entry points do
+fn state_from_python(object: &Bound<'_, PyAny>) -> PyResult<crate::GameState> {
+    let mut state: crate::GameState = from_python(object)?;
+    state.validate().map_err(|e| PyValueError::new_err(e.to_string()))?;
+    truncate_descriptions(&mut state, MAX_DESCRIPTION_LEN.load(Ordering::Relaxed));
+    Ok(state)
+}
+
+/// A game state, built from a dict with the fields of the JSON state
+#[pyclass(name = "GameState")]
+#[derive(Clone)]
+pub struct PyGameState {
+    inner: crate::GameState,
+}
+
+#[pymethods]
+impl PyGameState {
+    #[new]
+    fn new(state: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(Self { inner: state_from_python(state)? })
+    }
+
+    #[getter]
+    fn round(&self) -> u8 {
+        self.inner.round
+    }
+
+    #[getter]
+    fn phase(&self) -> String {
+        format!("{:?}", self.inner.phase)
+    }
+
+    #[getter]
+    fn player1_trust(&self) -> i32 {
+        self.inner.player1_trust
+    }
+
+    #[getter]
+    fn player2_trust(&self) -> i32 {
+        self.inner.player2_trust
+    }
+
+    fn to_dict(&self, py: Python<'_>) -> PyResult<PyObject> {
+        to_python(py, &self.inner)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "GameState(round={}, phase={:?}, player1_trust={}, player2_trust={})",
+            self.inner.round, self.inner.phase, self.inner.player1_trust, self.inner.player2_trust
+        )
+    }
+}
+
+/// A move; `claim` is a dict for claims and `None` otherwise
+#[pyclass(name = "Move")]
+#[derive(Clone)]
+pub struct PyMove {
+    inner: crate::Move,
+}
+
+#[pymethods]
+impl PyMove {
+    #[getter]
+    fn action(&self) -> String {
+        format!("{:?}", self.inner.action)
+    }
+
+    /// 1 or 2
+    #[getter]
+    fn player(&self) -> u8 {
+        self.inner.player.to_id()
+    }
+
+    #[getter]
+    fn confidence(&self) -> f64 {
+        self.inner.confidence
+    }
+
+    #[getter]
+    fn claim(&self, py: Python<'_>) -> PyResult<PyObject> {
+        to_python(py, &self.inner.claim)
+    }
+
+    fn to_dict(&self, py: Python<'_>) -> PyResult<PyObject> {
+        to_python(py, &self.inner)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Move(action={:?}, player={})", self.inner.action, self.inner.player.to_id())
+    }
+}
+
+/// Outcome of `search_optimal_move`
+#[pyclass(name = "SearchResult")]
+pub struct PySearchResult {
+    inner: crate::SearchResult,
+}
+
+#[pymethods]
+impl PySearchResult {
+    #[getter]
+    fn best_move(&self) -> PyMove {
+        PyMove { inner: self.inner.best_move.clone() }
+    }
+
+    #[getter]
+    fn evaluation(&self) -> f64 {
+        self.inner.evaluation
+    }
+
+    #[getter]
+    fn nodes_explored(&self) -> u64 {
+        self.inner.nodes_explored
+    }
+
+    #[getter]
+    fn depth_reached(&self) -> u8 {
+        self.inner.depth_reached
+    }
+
+    #[getter]
+    fn time_ms(&self) -> u64 {
+        self.inner.time_ms
+    }
+
+    #[getter]
+    fn principal_variation(&self) -> Vec<PyMove> {
+        self.inner
+            .principal_variation
+            .iter()
+            .map(|m| PyMove { inner: m.clone() })
+            .collect()
+    }
+
+    fn to_dict(&self, py: Python<'_>) -> PyResult<PyObject> {
+        to_python(py, &self.inner)
+    }
+}
+
+/// Search `state`, a `GameState` or a dict, `max_depth` plies deep for
+/// player 1 or 2 with parallel alpha-beta. Other Python threads keep
+/// running during the search.
+#[pyfunction]
+fn search_optimal_move(py: Python<'_>, state: &Bound<'_, PyAny>, max_depth: u8, player: u8) -> PyResult<PySearchResult> {
+    let state = match state.downcast::<PyGameState>() {
+        Ok(state) => state.borrow().inner.clone(),
+        Err(_) => state_from_python(state)?,
+    };
+    let player = player_from_id(player)?;
+
+    let result = py.allow_threads(|| AlphaBetaSearch::new(max_depth, true).search(&state, player));
+    Ok(PySearchResult { inner: result })
+}
+
+#[pymodule]
+fn strategic_mind_optimizer(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyGameState>()?;
+    module.add_class::<PyMove>()?;
+    module.add_class::<PySearchResult>()?;
+    module.add_function(wrap_pyfunction!(search_optimal_move, module)?)?;
+    Ok(())
+}
@@ -0,0 +1,206 @@
+use crate::{Claim, GameState, Player};
+use serde::{Deserialize, Serialize};
+
+/// Win, loss and round-limit thresholds for a game variant
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GameRules {
+    pub max_round: u8,
+    pub win_trust: i32,
+    pub lose_trust: i32,
+    /// At the round limit, a trust lead of at most this much is a draw.
+    /// With 0 only an exact tie is drawn.
+    pub draw_margin: i32,
+    /// What happens when a game that is not over offers no legal moves
+    pub no_move_policy: NoMovePolicy,
+    /// Chance that a claim of boldness 0 survives a challenge
+    pub challenge_base: f64,
+    /// How much each unit of boldness lowers that chance
+    pub challenge_boldness_factor: f64,
+    /// Chance that a challenge ends inconclusively: no trust changes hands
+    /// and the claim is withdrawn. 0 disables the outcome.
+    pub inconclusive_probability: f64,
+    /// Whether an inconclusive challenge still uses up the round
+    pub inconclusive_advances_round: bool,
+}
+
+/// Handling of a non-terminal position in which the player to move has no
+/// legal moves. Such a position is never scored as an ordinary leaf, since
+/// that would cut the search short without saying so.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum NoMovePolicy {
+    /// The player passes: the turn goes to the opponent with the state
+    /// unchanged, and the pass uses up one ply of search depth
+    #[default]
+    Pass,
+    /// The game is stuck and scored as a stalemate, worth 0 to both players
+    Stalemate,
+}
+
+/// How a finished game ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameOutcome {
+    Win(Player),
+    Draw,
+}
+
+/// Value of winning at the root. A game won `n` plies below the root is
+/// worth `MATE_SCORE - n` and one lost there `n - MATE_SCORE`, so faster
+/// wins and slower losses are preferred, and both outweigh any evaluation.
+pub const MATE_SCORE: f64 = 10_000.0;
+
+/// Value of a game won (or lost) `ply` plies below the root
+pub(crate) fn mate_value(won: bool, ply: usize) -> f64 {
+    let value = MATE_SCORE - ply as f64;
+    if won { value } else { -value }
+}
+
+impl Default for GameRules {
+    fn default() -> Self {
+        Self {
+            max_round: 20,
+            win_trust: 100,
+            lose_trust: -50,
+            draw_margin: 0,
+            no_move_policy: NoMovePolicy::Pass,
+            challenge_base: 0.6,
+            challenge_boldness_factor: 0.3,
+            inconclusive_probability: 0.0,
+            inconclusive_advances_round: false,
+        }
+    }
+}
+
+impl GameRules {
+    /// Decide the game, or `None` while it is still in progress.
+    /// Trust thresholds take priority over the round limit.
+    pub fn outcome(&self, state: &GameState) -> Option<GameOutcome> {
+        if state.player1_trust >= self.win_trust {
+            return Some(GameOutcome::Win(Player::Player1));
+        }
+        if state.player2_trust >= self.win_trust {
+            return Some(GameOutcome::Win(Player::Player2));
+        }
+        if state.player1_trust <= self.lose_trust {
+            return Some(GameOutcome::Win(Player::Player2));
+        }
+        if state.player2_trust <= self.lose_trust {
+            return Some(GameOutcome::Win(Player::Player1));
+        }
+
+        if state.round >= self.max_round {
+            let lead = state.player1_trust - state.player2_trust;
+
+            return Some(if lead.abs() <= self.draw_margin {
+                GameOutcome::Draw
+            } else if lead > 0 {
+                GameOutcome::Win(Player::Player1)
+            } else {
+                GameOutcome::Win(Player::Player2)
+            });
+        }
+
+        None
+    }
+
+    pub fn is_terminal(&self, state: &GameState) -> bool {
+        self.outcome(state).is_some()
+    }
+
+    /// Probability that `claim` is upheld when challenged, i.e. that the
+    /// claim succeeds and the challenger pays for it. Clamped to `[0, 1]`,
+    /// so out-of-range boldness from unvalidated input cannot produce an
+    /// impossible probability.
+    pub fn challenge_success_probability(&self, claim: &Claim) -> f64 {
+        (self.challenge_base - claim.boldness * self.challenge_boldness_factor).clamp(0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Phase;
+
+    fn create_test_state(round: u8, p1_trust: i32, p2_trust: i32) -> GameState {
+        GameState {
+            round,
+            phase: Phase::Claim,
+            player1_trust: p1_trust,
+            player2_trust: p2_trust,
+            current_claim: None,
+            move_history: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_outcome_in_progress() {
+        let rules = GameRules::default();
+        assert_eq!(rules.outcome(&create_test_state(5, 50, 50)), None);
+    }
+
+    #[test]
+    fn test_outcome_trust_thresholds() {
+        let rules = GameRules::default();
+        assert_eq!(
+            rules.outcome(&create_test_state(5, 100, 50)),
+            Some(GameOutcome::Win(Player::Player1))
+        );
+        assert_eq!(
+            rules.outcome(&create_test_state(5, 50, -50)),
+            Some(GameOutcome::Win(Player::Player1))
+        );
+    }
+
+    fn create_claim(boldness: f64) -> Claim {
+        Claim {
+            description: String::new(),
+            claim_type: crate::ClaimType::Prediction,
+            boldness,
+            is_bluff: false,
+        }
+    }
+
+    #[test]
+    fn test_challenge_success_probability() {
+        let rules = GameRules::default();
+
+        assert!((rules.challenge_success_probability(&create_claim(0.0)) - 0.6).abs() < 1e-9);
+        assert!((rules.challenge_success_probability(&create_claim(1.0)) - 0.3).abs() < 1e-9);
+        assert_eq!(rules.challenge_success_probability(&create_claim(2.5)), 0.0);
+        assert_eq!(rules.challenge_success_probability(&create_claim(-5.0)), 1.0);
+    }
+
+    #[test]
+    fn test_challenge_coefficients_configurable() {
+        let rules = GameRules {
+            challenge_base: 0.9,
+            challenge_boldness_factor: 0.5,
+            ..GameRules::default()
+        };
+
+        assert!((rules.challenge_success_probability(&create_claim(1.0)) - 0.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_draw_margin_at_round_limit() {
+        let state = create_test_state(20, 55, 50);
+
+        let strict = GameRules { draw_margin: 0, ..GameRules::default() };
+        let lenient = GameRules { draw_margin: 10, ..GameRules::default() };
+
+        assert_eq!(strict.outcome(&state), Some(GameOutcome::Win(Player::Player1)));
+        assert_eq!(lenient.outcome(&state), Some(GameOutcome::Draw));
+        assert_eq!(strict.outcome(&create_test_state(20, 50, 50)), Some(GameOutcome::Draw));
+    }
+
+    #[test]
+    fn test_custom_round_limit() {
+        let rules = GameRules { max_round: 5, ..GameRules::default() };
+
+        assert!(!rules.is_terminal(&create_test_state(4, 60, 50)));
+        assert_eq!(
+            rules.outcome(&create_test_state(5, 60, 50)),
+            Some(GameOutcome::Win(Player::Player1))
+        );
+        assert!(!GameRules::default().is_terminal(&create_test_state(5, 60, 50)));
+    }
+}
@@ -0,0 +1,198 @@
+use crate::{AlphaBetaSearch, GameState, GameTree, Move, Player};
+use crate::rules::{GameOutcome, GameRules, NoMovePolicy};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+/// Upper bound on game length, in case a rule set never terminates
+const MAX_GAME_PLIES: usize = 1000;
+
+/// Everything that happened in one simulated game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameRecord {
+    /// State once the random opening, if any, was played
+    pub opening_state: GameState,
+    pub final_state: GameState,
+    /// How the game ended, or `None` if it stopped without finishing
+    pub outcome: Option<GameOutcome>,
+    /// Every move played, opening included, in order, as recorded in the
+    /// final state's history
+    pub moves: Vec<Move>,
+}
+
+impl GameRecord {
+    /// Player who won, if the game ended in a win
+    pub fn winner(&self) -> Option<Player> {
+        match self.outcome {
+            Some(GameOutcome::Win(winner)) => Some(winner),
+            _ => None,
+        }
+    }
+}
+
+/// The position every game starts from
+pub fn standard_opening() -> GameState {
+    GameState::builder().build()
+}
+
+/// Play a full game from the standard opening under `rules`, `engine_a`
+/// moving for player 1 and `engine_b` for player 2, until it ends.
+/// Challenge outcomes are drawn from `seed`, so with seeded engines the
+/// same arguments replay the same game.
+pub fn simulate_game(engine_a: AlphaBetaSearch, engine_b: AlphaBetaSearch, rules: GameRules, seed: u64) -> GameRecord {
+    let mut engines = [engine_a.with_rules(rules), engine_b.with_rules(rules)];
+    play_game_with_random_opening(&mut engines, 0, seed)
+}
+
+/// Self-play a game from the standard opening, with the first
+/// `random_plies` plies chosen uniformly at random among the legal moves
+/// before `engines[0]` (player 1) and `engines[1]` (player 2) take over.
+///
+/// The opening moves and challenge outcomes are drawn from `seed`, so
+/// different seeds give a spread of starting positions for training data.
+/// The opening stops early rather than play into a finished game. The game
+/// is played under the first engine's rules.
+pub fn play_game_with_random_opening(
+    engines: &mut [AlphaBetaSearch; 2],
+    random_plies: u8,
+    seed: u64,
+) -> GameRecord {
+    let rules = *engines[0].rules();
+    let mut tree = GameTree::new_with_rules(standard_opening(), rules);
+    tree.set_seed(seed);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let mut state = standard_opening();
+    let mut to_move = Player::Player1;
+    let mut moves = Vec::new();
+
+    for _ in 0..random_plies {
+        let legal = tree.generate_moves(&state, to_move);
+        if legal.is_empty() {
+            break;
+        }
+
+        let candidate = &legal[rng.gen_range(0..legal.len())];
+        let mut next = state.clone();
+        tree.make_move(&mut next, candidate);
+        if tree.is_terminal(&next) {
+            break;
+        }
+
+        moves.extend(next.move_history.last().cloned());
+        state = next;
+        to_move = to_move.opponent();
+    }
+
+    let opening_state = state.clone();
+    let (final_state, outcome) = play_out(engines, &tree, &rules, state, to_move, &mut moves);
+
+    GameRecord {
+        opening_state,
+        final_state,
+        outcome,
+        moves,
+    }
+}
+
+/// Let the engines alternate from `state` until the game ends or gets stuck
+fn play_out(
+    engines: &mut [AlphaBetaSearch; 2],
+    tree: &GameTree,
+    rules: &GameRules,
+    mut state: GameState,
+    mut to_move: Player,
+    moves: &mut Vec<Move>,
+) -> (GameState, Option<GameOutcome>) {
+    let mut passed = false;
+
+    for _ in 0..MAX_GAME_PLIES {
+        if let Some(outcome) = rules.outcome(&state) {
+            return (state, Some(outcome));
+        }
+
+        if tree.generate_moves(&state, to_move).is_empty() {
+            // Stuck once both players have had to pass
+            if rules.no_move_policy == NoMovePolicy::Stalemate || passed {
+                break;
+            }
+            passed = true;
+            to_move = to_move.opponent();
+            continue;
+        }
+        passed = false;
+
+        let engine = match to_move {
+            Player::Player1 => &mut engines[0],
+            Player::Player2 => &mut engines[1],
+        };
+        let chosen = engine.search(&state, to_move).best_move;
+
+        tree.make_move(&mut state, &chosen);
+        moves.extend(state.move_history.last().cloned());
+        to_move = to_move.opponent();
+    }
+
+    let outcome = rules.outcome(&state);
+    (state, outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_engines() -> [AlphaBetaSearch; 2] {
+        [
+            AlphaBetaSearch::new(2, false).with_seed(1),
+            AlphaBetaSearch::new(2, false).with_seed(2),
+        ]
+    }
+
+    #[test]
+    fn test_random_openings_differ() {
+        let same_move = |a: &Move, b: &Move| {
+            a.action == b.action
+                && a.player == b.player
+                && a.claim.as_ref().map(|c| (c.claim_type, c.boldness))
+                    == b.claim.as_ref().map(|c| (c.claim_type, c.boldness))
+        };
+        let mut openings = Vec::new();
+
+        for seed in 0..4 {
+            let record = play_game_with_random_opening(&mut create_engines(), 2, seed);
+            let opening = record.opening_state;
+            assert_eq!(opening.move_history.len(), 2);
+
+            // Replaying the opening move by move only ever plays legal moves
+            let mut tree = GameTree::new(standard_opening());
+            tree.set_seed(seed);
+            let mut replay = standard_opening();
+            let mut to_move = Player::Player1;
+            for m in &opening.move_history {
+                let legal = tree.generate_moves(&replay, to_move);
+                assert!(legal.iter().any(|l| same_move(l, m)));
+                replay = tree.apply_move(&replay, m).unwrap();
+                to_move = to_move.opponent();
+            }
+
+            assert_eq!(replay.state_key(), opening.state_key());
+            assert!(!tree.is_terminal(&opening));
+            openings.push(opening.state_key());
+        }
+
+        openings.sort_unstable();
+        openings.dedup();
+        assert!(openings.len() > 1);
+    }
+
+    #[test]
+    fn test_simulated_game_terminates() {
+        let [a, b] = create_engines();
+        let record = simulate_game(a, b, GameRules::default(), 5);
+
+        assert!(record.outcome.is_some());
+        assert!(!record.moves.is_empty());
+        assert_eq!(record.final_state.move_history, record.moves);
+        assert!(GameRules::default().outcome(&record.final_state).is_some());
+    }
+}
@@ -0,0 +1,157 @@
+use crate::rules::{GameOutcome, GameRules};
+use crate::simulation::simulate_game;
+use crate::{AlphaBetaSearch, EvaluationWeights, Evaluator, Player, RiskProfile};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+/// One entrant of a tournament: how its engine searches
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EngineConfig {
+    pub name: String,
+    pub depth: u8,
+    pub weights: EvaluationWeights,
+    pub risk_profile: RiskProfile,
+}
+
+impl EngineConfig {
+    /// Entrant searching `depth` plies with the default weights and profile
+    pub fn new(name: impl Into<String>, depth: u8) -> Self {
+        Self {
+            name: name.into(),
+            depth,
+            weights: EvaluationWeights::default(),
+            risk_profile: RiskProfile::default(),
+        }
+    }
+
+    pub fn with_weights(mut self, weights: EvaluationWeights) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    pub fn with_risk_profile(mut self, risk_profile: RiskProfile) -> Self {
+        self.risk_profile = risk_profile;
+        self
+    }
+
+    fn engine(&self, seed: u64) -> AlphaBetaSearch {
+        AlphaBetaSearch::new(self.depth, false)
+            .with_evaluator(Evaluator::with_weights(self.weights.clone()))
+            .with_risk_profile(self.risk_profile)
+            .with_seed(seed)
+    }
+}
+
+/// Tally of one entrant. Games that stopped without an outcome count as
+/// draws.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Standing {
+    pub name: String,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+}
+
+impl Standing {
+    /// A point per win and half a point per draw
+    pub fn points(&self) -> f64 {
+        self.wins as f64 + self.draws as f64 / 2.0
+    }
+
+    pub fn games(&self) -> u32 {
+        self.wins + self.losses + self.draws
+    }
+}
+
+/// Final table of `run`, best entrant first
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Standings {
+    pub rows: Vec<Standing>,
+}
+
+impl Standings {
+    pub fn leader(&self) -> Option<&Standing> {
+        self.rows.first()
+    }
+}
+
+/// Play a round robin between `configs` under the default rules: every
+/// pair meets in `games_per_pair` games with each entrant as player 1 and
+/// as many as player 2. Entrants are ranked by points, then by wins;
+/// entrants still level keep the order they were given in.
+///
+/// Engine seeds and challenge outcomes are all drawn from `seed`, so the
+/// same configs and seed always give the same standings.
+pub fn run(configs: Vec<EngineConfig>, games_per_pair: u32, seed: u64) -> Standings {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut rows: Vec<Standing> = configs
+        .iter()
+        .map(|config| Standing {
+            name: config.name.clone(),
+            wins: 0,
+            losses: 0,
+            draws: 0,
+        })
+        .collect();
+
+    for first in 0..configs.len() {
+        for second in first + 1..configs.len() {
+            for _ in 0..games_per_pair {
+                for (player1, player2) in [(first, second), (second, first)] {
+                    let game_seed: u64 = rng.gen();
+                    let record = simulate_game(
+                        configs[player1].engine(game_seed),
+                        configs[player2].engine(game_seed.wrapping_add(1)),
+                        GameRules::default(),
+                        game_seed,
+                    );
+
+                    let (winner, loser) = match record.outcome {
+                        Some(GameOutcome::Win(Player::Player1)) => (player1, player2),
+                        Some(GameOutcome::Win(Player::Player2)) => (player2, player1),
+                        Some(GameOutcome::Draw) | None => {
+                            rows[player1].draws += 1;
+                            rows[player2].draws += 1;
+                            continue;
+                        }
+                    };
+                    rows[winner].wins += 1;
+                    rows[loser].losses += 1;
+                }
+            }
+        }
+    }
+
+    rows.sort_by(|a, b| b.points().total_cmp(&a.points()).then(b.wins.cmp(&a.wins)));
+    Standings { rows }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_robin_smoke() {
+        let configs = || {
+            vec![
+                EngineConfig::new("shallow", 1),
+                EngineConfig::new("deeper", 2),
+                EngineConfig::new("aggressive", 1).with_risk_profile(RiskProfile::Aggressive),
+            ]
+        };
+
+        let standings = run(configs(), 1, 3);
+        assert_eq!(standings.rows.len(), 3);
+
+        // Every entrant meets the other two once with each colour
+        assert!(standings.rows.iter().all(|row| row.games() == 4));
+        let wins: u32 = standings.rows.iter().map(|row| row.wins).sum();
+        let losses: u32 = standings.rows.iter().map(|row| row.losses).sum();
+        assert_eq!(wins, losses);
+        assert!(standings.rows.windows(2).all(|pair| pair[0].points() >= pair[1].points()));
+
+        // The same seed replays the same tournament
+        assert_eq!(run(configs(), 1, 3), standings);
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{AlphaBetaSearch, EvaluationWeights, Evaluator, Player};
+use crate::rules::GameOutcome;
+use crate::simulation::play_game_with_random_opening;
+use serde::{Deserialize, Serialize};
+
+/// Random plies played before the engines take over, so the games of a
+/// match do not all follow the same line
+const OPENING_PLIES: u8 = 2;
+
+/// Tally of a match between two weight sets, from `self_play`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchResult {
+    pub games: u32,
+    pub a_wins: u32,
+    pub b_wins: u32,
+    pub draws: u32,
+    /// Games that stopped without an outcome
+    pub unfinished: u32,
+}
+
+impl MatchResult {
+    /// Share of the points won by the first weight set, counting a draw or
+    /// unfinished game as half a point: 0.5 means the two are even
+    pub fn score_a(&self) -> f64 {
+        if self.games == 0 {
+            return 0.5;
+        }
+
+        let halves = self.draws + self.unfinished;
+        (self.a_wins as f64 + halves as f64 / 2.0) / self.games as f64
+    }
+}
+
+/// Play `games` full games between an evaluator using `weights_a` and one
+/// using `weights_b`, both searching `depth` plies, and tally the results.
+///
+/// The two sides swap colours every game and each game opens with a few
+/// random plies, all drawn from `seed`, so a match is reproducible and can
+/// serve as the fitness function of an external tuning loop.
+pub fn self_play(
+    weights_a: &EvaluationWeights,
+    weights_b: &EvaluationWeights,
+    games: u32,
+    depth: u8,
+    seed: u64,
+) -> MatchResult {
+    let mut result = MatchResult {
+        games,
+        ..MatchResult::default()
+    };
+
+    for game in 0..games {
+        let game_seed = seed.wrapping_add(game as u64);
+        let engine = |weights: &EvaluationWeights| {
+            AlphaBetaSearch::new(depth, false)
+                .with_evaluator(Evaluator::with_weights(weights.clone()))
+                .with_seed(game_seed)
+        };
+
+        // Weight set A plays first in even games
+        let a_player = if game % 2 == 0 { Player::Player1 } else { Player::Player2 };
+        let mut engines = match a_player {
+            Player::Player1 => [engine(weights_a), engine(weights_b)],
+            Player::Player2 => [engine(weights_b), engine(weights_a)],
+        };
+
+        match play_game_with_random_opening(&mut engines, OPENING_PLIES, game_seed).outcome {
+            Some(GameOutcome::Win(winner)) if winner == a_player => result.a_wins += 1,
+            Some(GameOutcome::Win(_)) => result.b_wins += 1,
+            Some(GameOutcome::Draw) => result.draws += 1,
+            None => result.unfinished += 1,
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_self_play_smoke() {
+        let defaults = EvaluationWeights::default();
+        let blind = EvaluationWeights::builder()
+            .trust_differential(0.0)
+            .trust_absolute(0.0)
+            .round_progress(0.0)
+            .position_advantage(0.0)
+            .build();
+
+        let result = self_play(&defaults, &blind, 4, 1, 11);
+        assert_eq!(result.games, 4);
+        assert_eq!(result.a_wins + result.b_wins + result.draws + result.unfinished, 4);
+        assert!((0.0..=1.0).contains(&result.score_a()));
+
+        // The same seed replays the same match
+        assert_eq!(self_play(&defaults, &blind, 4, 1, 11), result);
+    }
+}
@@ -0,0 +1,219 @@
+//! Zobrist hashing of game positions.
+//!
+//! Every component of a position (round, phase, each player's trust and
+//! the pending claim) has a fixed random key, and a position's hash is the
+//! XOR of the keys of its components. Trust can be anything up to
+//! `MAX_TRUST` either way, so rather than keeping a table its key is the
+//! player's key mixed with the trust value. Changing one component therefore
+//! only takes XORing its old key out and its new key in, which lets the
+//! search update a hash move by move instead of rehashing the state.
+//! Like `GameState::position_key`, the hash ignores the move history.
+
+use crate::{Claim, ClaimType, GameState, Phase, Player};
+use std::sync::OnceLock;
+
+/// Boldness is hashed in tenths, from 0.0 to 1.0
+const BOLDNESS_BUCKETS: usize = 11;
+
+/// Fixed seed, so hashes are the same in every run and on every platform
+const KEY_SEED: u64 = 0x5a0b_7157_c0de_2024;
+
+struct ZobristKeys {
+    round: [u64; 256],
+    phase: [u64; 3],
+    trust: [u64; 2],
+    claim_type: [u64; 4],
+    boldness: [u64; BOLDNESS_BUCKETS],
+    bluff: u64,
+}
+
+fn keys() -> &'static ZobristKeys {
+    static KEYS: OnceLock<ZobristKeys> = OnceLock::new();
+
+    KEYS.get_or_init(|| {
+        let mut state = KEY_SEED;
+        let mut next = || splitmix64(&mut state);
+
+        ZobristKeys {
+            round: std::array::from_fn(|_| next()),
+            phase: std::array::from_fn(|_| next()),
+            trust: std::array::from_fn(|_| next()),
+            claim_type: std::array::from_fn(|_| next()),
+            boldness: std::array::from_fn(|_| next()),
+            bluff: next(),
+        }
+    })
+}
+
+/// SplitMix64, chosen over `rand` so the keys never change with a dependency
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+pub fn round_key(round: u8) -> u64 {
+    keys().round[round as usize]
+}
+
+pub fn phase_key(phase: Phase) -> u64 {
+    let index = match phase {
+        Phase::Claim => 0,
+        Phase::Challenge => 1,
+        Phase::Resolution => 2,
+    };
+    keys().phase[index]
+}
+
+/// SplitMix64 is a bijection of its state, so every trust value of a
+/// player gets a key of its own
+pub fn trust_key(player: Player, trust: i32) -> u64 {
+    let index = match player {
+        Player::Player1 => 0,
+        Player::Player2 => 1,
+    };
+    let mut state = keys().trust[index] ^ trust as i64 as u64;
+    splitmix64(&mut state)
+}
+
+/// Whether `boldness` lies on a tenth, so it hashes apart from any other
+/// such boldness
+pub fn on_boldness_grid(boldness: f64) -> bool {
+    let tenths = boldness * 10.0;
+    (tenths - tenths.round()).abs() < 1e-9
+}
+
+/// Key of the pending claim; no claim hashes to 0
+pub fn claim_key(claim: Option<&Claim>) -> u64 {
+    let Some(claim) = claim else {
+        return 0;
+    };
+    let keys = keys();
+
+    let type_index = match claim.claim_type {
+        ClaimType::Information => 0,
+        ClaimType::Prediction => 1,
+        ClaimType::Accusation => 2,
+        ClaimType::Alliance => 3,
+    };
+    let boldness = ((claim.boldness * 10.0).round() as i64).clamp(0, BOLDNESS_BUCKETS as i64 - 1) as usize;
+    let bluff = if claim.is_bluff { keys.bluff } else { 0 };
+
+    keys.claim_type[type_index] ^ keys.boldness[boldness] ^ bluff
+}
+
+/// Hash of `state` computed from scratch
+pub fn hash(state: &GameState) -> u64 {
+    round_key(state.round)
+        ^ phase_key(state.phase)
+        ^ trust_key(Player::Player1, state.player1_trust)
+        ^ trust_key(Player::Player2, state.player2_trust)
+        ^ claim_key(state.current_claim.as_ref())
+}
+
+/// Turn `hash`, the hash of `before`, into the hash of `after`, touching
+/// only the components that differ between them
+pub fn update(hash: u64, before: &GameState, after: &GameState) -> u64 {
+    let mut hash = hash;
+
+    if before.round != after.round {
+        hash ^= round_key(before.round) ^ round_key(after.round);
+    }
+    if before.phase != after.phase {
+        hash ^= phase_key(before.phase) ^ phase_key(after.phase);
+    }
+    if before.player1_trust != after.player1_trust {
+        hash ^= trust_key(Player::Player1, before.player1_trust) ^ trust_key(Player::Player1, after.player1_trust);
+    }
+    if before.player2_trust != after.player2_trust {
+        hash ^= trust_key(Player::Player2, before.player2_trust) ^ trust_key(Player::Player2, after.player2_trust);
+    }
+    if before.current_claim != after.current_claim {
+        hash ^= claim_key(before.current_claim.as_ref()) ^ claim_key(after.current_claim.as_ref());
+    }
+
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Action, GameTree, Move, MAX_TRUST};
+
+    fn create_test_state() -> GameState {
+        GameState {
+            round: 3,
+            phase: Phase::Claim,
+            player1_trust: 50,
+            player2_trust: 50,
+            current_claim: None,
+            move_history: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_equal_states_hash_equal() {
+        let mut with_history = create_test_state();
+        with_history.move_history.push(Move {
+            action: Action::Accept,
+            player: Player::Player2,
+            claim: None,
+            confidence: 0.6,
+            trust_after: None,
+        });
+
+        assert_eq!(create_test_state().zobrist_hash(), create_test_state().zobrist_hash());
+        assert_eq!(with_history.zobrist_hash(), create_test_state().zobrist_hash());
+
+        let mut swapped = create_test_state();
+        swapped.player1_trust = 60;
+        let mut mirrored = create_test_state();
+        mirrored.player2_trust = 60;
+        assert_ne!(swapped.zobrist_hash(), mirrored.zobrist_hash());
+    }
+
+    #[test]
+    fn test_trust_hashes_apart_across_validated_range() {
+        for (low, high) in [(300, 400), (-200, -300), (255, 256), (-MAX_TRUST, MAX_TRUST)] {
+            for player in [Player::Player1, Player::Player2] {
+                let with_trust = |trust| {
+                    let mut state = create_test_state();
+                    match player {
+                        Player::Player1 => state.player1_trust = trust,
+                        Player::Player2 => state.player2_trust = trust,
+                    }
+                    assert!(state.validate().is_ok());
+                    state
+                };
+                assert_ne!(with_trust(low).zobrist_hash(), with_trust(high).zobrist_hash());
+            }
+        }
+    }
+
+    #[test]
+    fn test_incremental_update_round_trips() {
+        let state = create_test_state();
+        let tree = GameTree::with_seed(state.clone(), 1);
+        let original = state.zobrist_hash();
+
+        let mut current = state.clone();
+        let mut hash = original;
+        // A claim, then a challenge of it
+        for (player, index) in [(Player::Player1, 5), (Player::Player2, 0)] {
+            let chosen = tree.generate_moves(&current, player).swap_remove(index);
+            let (next, next_hash) = tree.apply_move_hashed(&current, hash, &chosen);
+            assert_eq!(next_hash, next.zobrist_hash());
+
+            // Reversing the transition restores the original hash
+            assert_eq!(update(next_hash, &next, &current), hash);
+
+            current = next;
+            hash = next_hash;
+        }
+
+        assert_ne!(hash, original);
+        assert_eq!(update(hash, &current, &state), original);
+    }
+}
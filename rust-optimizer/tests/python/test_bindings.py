@@ -0,0 +1,46 @@
+"""Smoke tests for the Python bindings.
+
+Build the extension into the current environment first, for example with
+`maturin develop --features python` from the rust-optimizer directory.
+"""
+
+import pytest
+
+optimizer = pytest.importorskip("strategic_mind_optimizer")
+
+
+def opening_state():
+    return {
+        "round": 1,
+        "phase": "Claim",
+        "player1_trust": 50,
+        "player2_trust": 50,
+        "current_claim": None,
+        "move_history": [],
+    }
+
+
+def test_search_from_dict():
+    result = optimizer.search_optimal_move(opening_state(), 3, 1)
+
+    assert result.best_move.player == 1
+    assert result.best_move.action == "MakeClaim"
+    assert isinstance(result.best_move.claim, dict)
+    assert result.nodes_explored > 0
+    assert result.principal_variation[0].to_dict() == result.best_move.to_dict()
+    assert result.to_dict()["depth_reached"] == result.depth_reached
+
+
+def test_search_from_game_state():
+    state = optimizer.GameState(opening_state())
+    assert state.to_dict() == opening_state()
+
+    result = optimizer.search_optimal_move(state, 2, 1)
+    assert -100.0 <= result.evaluation <= 100.0
+
+
+def test_invalid_input_raises():
+    with pytest.raises(ValueError):
+        optimizer.search_optimal_move(opening_state(), 2, 3)
+    with pytest.raises(ValueError):
+        optimizer.GameState({"round": 1})